@@ -0,0 +1,125 @@
+# Deutsch. `cargo run -- --lang de` writes the German decks into localized/de/.
+# The element forms are used as { $element-dative } and so on, see src/localization.rs.
+
+element-field = Feld
+element-field-plural = Felder
+element-field-accusative = ein Feld
+element-field-dative = einem Feld
+element-field-relative = das
+element-forest = Wald
+element-forest-plural = Wälder
+element-forest-accusative = einen Wald
+element-forest-dative = einem Wald
+element-forest-relative = der
+element-mountain = Berg
+element-mountain-plural = Berge
+element-mountain-accusative = einen Berg
+element-mountain-dative = einem Berg
+element-mountain-relative = der
+element-volcano = Vulkan
+element-volcano-plural = Vulkane
+element-volcano-accusative = einen Vulkan
+element-volcano-dative = einem Vulkan
+element-volcano-relative = der
+element-lake = See
+element-lake-plural = Seen
+element-lake-accusative = einen See
+element-lake-dative = einem See
+element-lake-relative = der
+element-ice = Eis
+element-ice-plural = Eis
+element-ice-accusative = Eis
+element-ice-dative = Eis
+element-ice-relative = das
+element-tomb = Grab
+element-tomb-plural = Gräber
+element-tomb-accusative = ein Grab
+element-tomb-dative = einem Grab
+element-tomb-relative = das
+element-void = Leere
+element-void-plural = Leeren
+element-void-accusative = eine Leere
+element-void-dative = einer Leere
+element-void-relative = die
+
+## ends
+
+end-each = { $scores } Punkt pro { $element }
+end-adjacent = 2 Punkte für jedes benachbarte Paar aus { $a } und { $b }
+end-just-1 = { $scores } Punkte, wenn es am Ende genau { $element-accusative } gibt
+end-chain = 1 Punkt für jedes Land in einer Kette aus { $a }, { $b }, { $c } (Ketten dürfen sich überschneiden, aber kein Land zählt mehr als einmal)
+end-just-2 = { $scores } Punkte, solange es am Ende genau 2 { $element-plural } gibt
+end-triangle = { $scores } Punkte für jedes Dreieck benachbarter Länder aus { $a }, { $b } und { $c }
+end-interventionist = Dir ist jede Kleinigkeit wichtig. 8 Punkte für jeden See, 7 für jedes Grab und so weiter; Berg: 6, Vulkan: 5, Eis: 4, Wald: 3, Leere: 2, Feld: 1, und 2 Punkte für jeden überlebenden Agenten.
+end-banks = für alle { $a-plural } und { $b-plural } am Ufer einer Gruppe aus { $road }: zähle die Anzahl der { $a-plural } mal die Anzahl der { $b-plural }
+end-cluster = 1 Punkt pro { $element } in der größten zusammenhängenden Gruppe aus { $element-plural } ({ $element-plural } außerhalb dieser Gruppe sind wertlos)
+end-forbid = { $scores } Punkte, wenn es überhaupt keine { $element-plural } gibt
+end-never-adjacent = { $scores } Punkte, wenn am Ende nirgends { $a } an { $b } grenzt
+end-isolated = { $scores } Punkte pro { $a }, { $a-relative } nicht an { $b } grenzt
+end-isolated-2 = { $scores } Punkte pro { $a }, { $a-relative } weder an { $b } noch an { $c } grenzt
+end-scavenger = ein schrecklicher Hunger.
+    4 Punkte für jede Leiche, die du am Ende besitzt (Töten erzeugt Leichen, Leichen können als Gegenstände verstaut und herumgetragen werden)
+end-altruism = Deine Werte umfassen die Werte der anderen.
+
+    Zähle die Summe der Punkte aller anderen.
+end-patch = 1 Punkt pro { $a } oder { $b } in der größten zusammenhängenden Fläche dieser Landarten.
+end-dog-altruism = Du teilst die Wünsche der Spieler neben dir, aber nur, solange sie neben dir sind. Sobald ihr getrennt seid, sind dir diese Dinge egal.
+
+## means
+
+means-ambush-name = { $element }-Hinterhalt
+means-ambush = wenn du in { $element-dative } stehst, töte einen Agenten in der Nähe
+means-slaying-name = Erschlagen
+means-slaying = Töte einen Agenten im selben Land wie du, wenn du nahe bei { $a }/{ $b } bist
+means-transit-name = Wandel zu { $element }
+means-transit-nearby = wenn du nahe bei { $opposite-dative } stehst, verwandle es in { $element-accusative }
+means-transit-any = verwandle ein beliebiges Land vom Typ { $opposite } in { $element }
+means-bloom-name = { $ring }-Blüte
+means-bloom = nahe bei { $support-dative }: verwandle es, und verwandle alle { $ring-plural } in deiner Nähe
+means-prism-name = Prisma
+means-prism = wenn du nahe bei einem Paar aus { $a } und { $b } stehst, kann ein Land, das an beide grenzt, oder eines der beiden Länder des Paars verwandelt werden
+means-transit-either-name = Wandel { $element }/{ $opposite }
+means-transit-either = wenn du in { $support-dative } stehst, verwandle ein nahes Land vom Typ { $opposite }/{ $element }
+means-transit-supported = wenn du in { $support-dative } stehst, verwandle ein nahes Land vom Typ { $opposite } in { $element }
+means-catastrophe-name = { $element }-Katastrophe
+means-catastrophe = Wenn du in { $from-dative } stehst, verwandle es, und verwandle ein Land vom Typ { $to } bis zu drei Länder entfernt. Alles nahe diesem entfernten Land stirbt.
+means-dominion-around-name = Herrschaft um { $element-accusative }
+means-dominion-around = töte beliebig viele Agenten nahe bei { $element-dative }, { $element-relative } an { $support } grenzt.
+means-dominion-name = Herrschaft über { $element-plural }
+means-dominion = töte beliebig viele Agenten, die auf { $element-plural } stehen.
+means-dominion-either-name = Herrschaft über { $a }/{ $b }
+means-dominion-either = töte beliebig viele Agenten auf Land vom Typ { $a }/{ $b }, das an { $support } grenzt.
+means-tyranny-name = Tyrannei
+means-tyranny = wenn du in { $element-dative } nahe bei { $support-dative } stehst, töte einen Agenten bis zu zwei Schritte von dir entfernt.
+means-tyrant-shot-name = Tyrannenschuss
+means-tyrant-shot = wenn du in { $element-dative } nahe bei { $support-dative } stehst, töte einen Agenten bis zu zwei Schritte von dir entfernt und verwandle dabei das Land vom Typ { $support }.
+means-domain-name = Domäne
+means-domain = wenn du in { $element-dative } nahe bei { $support-dative } stehst, verwandle ein Land im Umkreis von 2 Ländern.
+means-domain-burn-name = Domänenbrand
+means-domain-burn = wenn du in { $element-dative } nahe bei { $support-dative } stehst, verwandle dieses Land vom Typ { $support } und ein beliebiges anderes Land im Umkreis von 2 Ländern.
+means-hot-cast-name = Heißer Wurf
+means-hot-cast = wenn du in { $element-dative } stehst, verwandle es und ein Land im Umkreis von 2 Ländern.
+means-domain-smaller = wenn du in { $element-dative } nahe bei { $support-dative } stehst, verwandle ein nahes Land.
+means-domain-smaller-exchange = wenn du in { $element-dative } nahe bei { $support-dative } stehst, verwandle das Land vom Typ { $support } und ein beliebiges anderes nahes Land.
+means-domain-any = wenn du auf { $element-dative } stehst, verwandle ein Land im Umkreis von 2 Ländern.
+means-wind-name = { $element }-Wind
+means-wind-flip = verwandle ein nahes Land vom Typ { $opposite } in { $element } und bewege jeden darauf um bis zu drei Länder
+means-wind-flip-smaller = verwandle ein nahes Land vom Typ { $opposite } in { $element } und bewege jeden darauf um bis zu zwei Länder
+means-reaction-wind-name = Reaktionswind
+means-reaction-wind = bewege einen Agenten, der nahe bei einem Paar aus { $a } und { $b } steht, um drei Länder.
+means-wind = bewege jeden auf einem nahen Land vom Typ { $element } um bis zu drei Länder
+means-wind-any = bewege jeden auf { $element-dative } um bis zu zwei Länder
+means-freezing-wind-name = Eiswind
+means-freezing-wind = lass ein beliebiges Land vom Typ { $lake } gefrieren und bewege jeden darauf um bis zu drei Länder
+means-travel-name = { $element }-Reise
+means-travel = schicke einen Agenten, der auf { $element-dative } steht, zu einem beliebigen anderen Land vom Typ { $element }
+means-liftoff-name = { $element }-Abflug
+means-liftoff = wähle ein beliebiges Land vom Typ { $from } und schicke jeden darauf zu einem beliebigen Land vom Typ { $to }. Das Ausgangsland wird verwandelt.
+means-banishment-name = { $element }-Verbannung
+means-banishment = schicke jeden auf einem nahen Land zu einem beliebigen Land vom Typ { $element }
+means-flip-all-name = Alles verwandeln
+means-flip-all = wenn du auf einer Kette aus { $a }, { $b }, { $c } stehst, verwandle sie alle.
+means-flip-both-name = Beide verwandeln
+means-flip-both = Wenn du auf einem Paar aus { $a } und { $b } stehst, verwandle beide.
+means-atoms-name = Atome für etwas anderes
+means-atoms = Nahe bei { $element-dative }, { $element-relative } auch nahe bei einem gegnerischen Agenten ist: fange diesen Agenten und ersetze ihn durch einen eigenen. Alle deine Agenten können nun die Fähigkeiten dieses Spielers nutzen.
@@ -0,0 +1,673 @@
+// the board as a hex grid of lands, the rules that desire cards score by, and the dealing of boards under constraints
+
+use std::{cell::RefCell, collections::HashMap};
+
+use rand::{seq::SliceRandom, Rng};
+
+use super::*;
+
+// how a desire (end) card scores over a board. These don't cover agents or items, only the lands.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Desire {
+    // points for every land of the element
+    Each(ElementTag, f64),
+    // points for every adjacent pairing of the two elements
+    AdjacentPairs(ElementTag, ElementTag, f64),
+    // points if there are exactly this many of the element
+    Exactly(ElementTag, usize, f64),
+    // 1 point for each land included in a chain of a, b, c (b adjacent to both the a and the c)
+    Chain(ElementTag, ElementTag, ElementTag),
+    // points for every triangle of mutually adjacent lands of these three elements
+    Triangle(ElementTag, ElementTag, ElementTag, f64),
+    // 1 point for each land in the largest connected cluster of the element
+    LargestCluster(ElementTag),
+    // points if there's none of the element at all
+    Absent(ElementTag, f64),
+    // points if no land of the first element is adjacent to any of the second
+    NeverAdjacent(ElementTag, ElementTag, f64),
+    // points per land of the first element that isn't adjacent to any of the others
+    Isolated(ElementTag, Vec<ElementTag>, f64),
+    // 1 point for each land in the largest connected patch of either element
+    LargestPatch(ElementTag, ElementTag),
+    // (bank, road, bank): for each clump of road, the number of the first on its banks multiplied by the number of the second
+    Banks(ElementTag, ElementTag, ElementTag),
+    // points per land, by element
+    Weighted([f64; 8]),
+}
+
+pub struct DesireScore {
+    pub points: f64,
+    // indices into Board::tiles of the lands that earned the points
+    pub tiles: Vec<usize>,
+}
+
+impl Desire {
+    pub fn evaluate(&self, board: &Board) -> DesireScore {
+        let of = |e: ElementTag| -> Vec<usize> { board.indices_of(e).collect() };
+        match self {
+            Desire::Each(e, p) => {
+                let tiles = of(*e);
+                DesireScore {
+                    points: tiles.len() as f64 * p,
+                    tiles,
+                }
+            }
+            Desire::AdjacentPairs(a, b, p) => {
+                let mut tiles = Vec::new();
+                let mut pairs = 0;
+                for (i, j) in board.adjacent_pairs() {
+                    let (ei, ej) = (board.tiles[i].1, board.tiles[j].1);
+                    if (ei == *a && ej == *b) || (ei == *b && ej == *a) {
+                        pairs += 1;
+                        tiles.push(i);
+                        tiles.push(j);
+                    }
+                }
+                DesireScore {
+                    points: pairs as f64 * p,
+                    tiles: dedup(tiles),
+                }
+            }
+            Desire::Exactly(e, n, p) => {
+                let tiles = of(*e);
+                if tiles.len() == *n {
+                    DesireScore { points: *p, tiles }
+                } else {
+                    DesireScore::nothing()
+                }
+            }
+            Desire::Chain(a, b, c) => {
+                let mut tiles = Vec::new();
+                for middle in board.indices_of(*b) {
+                    let ends_a: Vec<usize> = board
+                        .adjacent(middle)
+                        .filter(|i| board.tiles[*i].1 == *a)
+                        .collect();
+                    let ends_c: Vec<usize> = board
+                        .adjacent(middle)
+                        .filter(|i| board.tiles[*i].1 == *c)
+                        .collect();
+                    for ea in ends_a.iter() {
+                        for ec in ends_c.iter() {
+                            if ea != ec {
+                                tiles.extend([*ea, middle, *ec]);
+                            }
+                        }
+                    }
+                }
+                let tiles = dedup(tiles);
+                DesireScore {
+                    points: tiles.len() as f64,
+                    tiles,
+                }
+            }
+            Desire::Triangle(a, b, c, p) => {
+                let mut wanted = [*a, *b, *c];
+                wanted.sort();
+                let mut tiles = Vec::new();
+                let mut count = 0;
+                for (i, j) in board.adjacent_pairs() {
+                    for k in board.adjacent(i) {
+                        // only count each triangle once, from its smallest two indices
+                        if k <= j || !board.adjacent(j).any(|jk| jk == k) {
+                            continue;
+                        }
+                        let mut have = [board.tiles[i].1, board.tiles[j].1, board.tiles[k].1];
+                        have.sort();
+                        if have == wanted {
+                            count += 1;
+                            tiles.extend([i, j, k]);
+                        }
+                    }
+                }
+                DesireScore {
+                    points: count as f64 * p,
+                    tiles: dedup(tiles),
+                }
+            }
+            Desire::LargestCluster(e) => {
+                let tiles = board
+                    .clusters(|t| t == *e)
+                    .into_iter()
+                    .max_by_key(|c| c.len())
+                    .unwrap_or_default();
+                DesireScore {
+                    points: tiles.len() as f64,
+                    tiles,
+                }
+            }
+            Desire::Absent(e, p) => {
+                if board.count(*e) == 0 {
+                    DesireScore {
+                        points: *p,
+                        tiles: vec![],
+                    }
+                } else {
+                    DesireScore::nothing()
+                }
+            }
+            Desire::NeverAdjacent(a, b, p) => {
+                if board.adjacent_pairs().any(|(i, j)| {
+                    let (ei, ej) = (board.tiles[i].1, board.tiles[j].1);
+                    (ei == *a && ej == *b) || (ei == *b && ej == *a)
+                }) {
+                    DesireScore::nothing()
+                } else {
+                    DesireScore {
+                        points: *p,
+                        tiles: vec![],
+                    }
+                }
+            }
+            Desire::Isolated(e, others, p) => {
+                let tiles: Vec<usize> = board
+                    .indices_of(*e)
+                    .filter(|i| !board.adjacent(*i).any(|j| others.contains(&board.tiles[j].1)))
+                    .collect();
+                DesireScore {
+                    points: tiles.len() as f64 * p,
+                    tiles,
+                }
+            }
+            Desire::LargestPatch(a, b) => {
+                let tiles = board
+                    .clusters(|t| t == *a || t == *b)
+                    .into_iter()
+                    .max_by_key(|c| c.len())
+                    .unwrap_or_default();
+                DesireScore {
+                    points: tiles.len() as f64,
+                    tiles,
+                }
+            }
+            Desire::Banks(a, road, b) => {
+                let mut points = 0.0;
+                let mut tiles = Vec::new();
+                for clump in board.clusters(|t| t == *road) {
+                    let banks = dedup(
+                        clump
+                            .iter()
+                            .flat_map(|i| board.adjacent(*i))
+                            .filter(|j| {
+                                let ej = board.tiles[*j].1;
+                                ej == *a || ej == *b
+                            })
+                            .collect(),
+                    );
+                    let na = banks.iter().filter(|j| board.tiles[**j].1 == *a).count();
+                    let nb = banks.iter().filter(|j| board.tiles[**j].1 == *b).count();
+                    if na > 0 && nb > 0 {
+                        points += (na * nb) as f64;
+                        tiles.extend(clump);
+                        tiles.extend(banks);
+                    }
+                }
+                DesireScore {
+                    points,
+                    tiles: dedup(tiles),
+                }
+            }
+            Desire::Weighted(by_element) => DesireScore {
+                points: board.tiles.iter().map(|(_, e)| by_element[*e]).sum(),
+                tiles: (0..board.tiles.len()).collect(),
+            },
+        }
+    }
+    pub fn score(&self, board: &Board) -> f64 {
+        self.evaluate(board).points
+    }
+    // the variant's name in kebab case, as parse reads it
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Desire::Each(..) => "each",
+            Desire::AdjacentPairs(..) => "adjacent-pairs",
+            Desire::Exactly(..) => "exactly",
+            Desire::Chain(..) => "chain",
+            Desire::Triangle(..) => "triangle",
+            Desire::LargestCluster(..) => "largest-cluster",
+            Desire::Absent(..) => "absent",
+            Desire::NeverAdjacent(..) => "never-adjacent",
+            Desire::Isolated(..) => "isolated",
+            Desire::LargestPatch(..) => "largest-patch",
+            Desire::Banks(..) => "banks",
+            Desire::Weighted(..) => "weighted",
+        }
+    }
+    // from the command line, the variant's name in kebab case then its fields, separated by colons, eg "each:field:1.5", "chain:field:forest:lake", "isolated:tomb:field,forest:2". Weighted takes the eight weights separated by commas.
+    pub fn parse(s: &str) -> Result<Desire, String> {
+        let mut fields = s.split(':');
+        let kind = fields.next().unwrap_or_default();
+        let fields: Vec<&str> = fields.collect();
+        let wrong = || format!("couldn't read the desire {s:?}");
+        let field = |i: usize| fields.get(i).copied().ok_or_else(wrong);
+        let e = |i: usize| field(i).and_then(parse_element);
+        let n = |i: usize| field(i)?.parse::<f64>().map_err(|_| wrong());
+        let d = match kind {
+            "each" => Desire::Each(e(0)?, n(1)?),
+            "adjacent-pairs" => Desire::AdjacentPairs(e(0)?, e(1)?, n(2)?),
+            "exactly" => Desire::Exactly(e(0)?, field(1)?.parse().map_err(|_| wrong())?, n(2)?),
+            "chain" => Desire::Chain(e(0)?, e(1)?, e(2)?),
+            "triangle" => Desire::Triangle(e(0)?, e(1)?, e(2)?, n(3)?),
+            "largest-cluster" => Desire::LargestCluster(e(0)?),
+            "absent" => Desire::Absent(e(0)?, n(1)?),
+            "never-adjacent" => Desire::NeverAdjacent(e(0)?, e(1)?, n(2)?),
+            "isolated" => Desire::Isolated(
+                e(0)?,
+                field(1)?.split(',').map(parse_element).collect::<Result<_, _>>()?,
+                n(2)?,
+            ),
+            "largest-patch" => Desire::LargestPatch(e(0)?, e(1)?),
+            "banks" => Desire::Banks(e(0)?, e(1)?, e(2)?),
+            "weighted" => {
+                let ws: Vec<f64> = field(0)?
+                    .split(',')
+                    .map(|w| w.parse::<f64>().map_err(|_| wrong()))
+                    .collect::<Result<_, _>>()?;
+                Desire::Weighted(ws.try_into().map_err(|_| format!("{s:?} needs a weight for each of the 8 elements"))?)
+            }
+            _ => return Err(format!("{s:?} isn't a kind of desire")),
+        };
+        Ok(d)
+    }
+}
+
+fn parse_element(name: &str) -> Result<ElementTag, String> {
+    ELEMENT_NAMES
+        .iter()
+        .position(|n| *n == name)
+        .ok_or_else(|| format!("there's no element called {name:?}, there's {}", ELEMENT_NAMES.join(", ")))
+}
+
+impl DesireScore {
+    fn nothing() -> Self {
+        DesireScore {
+            points: 0.0,
+            tiles: vec![],
+        }
+    }
+}
+
+fn dedup(mut v: Vec<usize>) -> Vec<usize> {
+    v.sort();
+    v.dedup();
+    v
+}
+
+#[derive(Clone)]
+pub struct Board {
+    pub tiles: Vec<(Coord, ElementTag)>,
+    at: HashMap<Coord, usize>,
+}
+
+impl Board {
+    pub fn new(tiles: Vec<(Coord, ElementTag)>) -> Self {
+        let at = tiles.iter().enumerate().map(|(i, (c, _))| (*c, i)).collect();
+        Board { tiles, at }
+    }
+    // lays the elements out in the order of a HexSpiral, which is how generate_board has always placed them
+    pub fn hexagon(radius: usize, elements: &[ElementTag]) -> Self {
+        Board::new(
+            HexSpiral::new()
+                .layer_iter(radius)
+                .zip(elements.iter().cloned())
+                .collect(),
+        )
+    }
+    pub fn element_at(&self, c: Coord) -> Option<ElementTag> {
+        self.at.get(&c).map(|i| self.tiles[*i].1)
+    }
+    pub fn adjacent(&self, i: usize) -> impl Iterator<Item = usize> + '_ {
+        self.tiles[i]
+            .0
+            .neighbors()
+            .filter_map(|c| self.at.get(&c).cloned())
+    }
+    // every adjacent pair of tiles, once each
+    pub fn adjacent_pairs(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.tiles.len()).flat_map(move |i| {
+            self.adjacent(i)
+                .filter(move |j| *j > i)
+                .map(move |j| (i, j))
+        })
+    }
+    pub fn indices_of(&self, e: ElementTag) -> impl Iterator<Item = usize> + '_ {
+        (0..self.tiles.len()).filter(move |i| self.tiles[*i].1 == e)
+    }
+    pub fn count(&self, e: ElementTag) -> usize {
+        self.indices_of(e).count()
+    }
+    // connected groups of the tiles whose element passes `member`
+    pub fn clusters(&self, member: impl Fn(ElementTag) -> bool) -> Vec<Vec<usize>> {
+        let mut seen = vec![false; self.tiles.len()];
+        let mut r = Vec::new();
+        for start in 0..self.tiles.len() {
+            if seen[start] || !member(self.tiles[start].1) {
+                continue;
+            }
+            seen[start] = true;
+            let mut cluster = vec![start];
+            let mut frontier = vec![start];
+            while let Some(i) = frontier.pop() {
+                for j in self.adjacent(i) {
+                    if !seen[j] && member(self.tiles[j].1) {
+                        seen[j] = true;
+                        cluster.push(j);
+                        frontier.push(j);
+                    }
+                }
+            }
+            r.push(cluster);
+        }
+        r
+    }
+}
+
+// all of these are off by default, in which case a board is just a shuffle
+#[derive(Clone, Debug)]
+pub struct BoardConstraints {
+    // no connected cluster of a single element may be larger than this
+    pub max_cluster_size: Option<usize>,
+    // every element that can appear on the board must appear at least this many times
+    pub min_each_element: Option<usize>,
+    // no land may be adjacent to its opposite (field beside forest, lake beside ice, etc)
+    pub no_opposed_adjacent: bool,
+    // each of these must be able to score at least min_desire_points on the board as it's dealt
+    pub dealt_desires: Vec<Desire>,
+    pub min_desire_points: f64,
+    // how many fresh shuffles to try before falling back to repairing the best of them
+    pub max_attempts: usize,
+    // how many swaps and flips the repair search may try
+    pub max_repair_steps: usize,
+}
+impl Default for BoardConstraints {
+    fn default() -> Self {
+        Self {
+            max_cluster_size: None,
+            min_each_element: None,
+            no_opposed_adjacent: false,
+            dealt_desires: vec![],
+            min_desire_points: 0.0,
+            max_attempts: 200,
+            max_repair_steps: 4000,
+        }
+    }
+}
+impl BoardConstraints {
+    // how badly the board breaks the constraints. 0.0 means they're all satisfied.
+    pub fn violation(&self, board: &Board, suppress_voids: bool) -> f64 {
+        let mut total = 0.0;
+        if let Some(max) = self.max_cluster_size {
+            for e in 0..8 {
+                for c in board.clusters(|t| t == e) {
+                    total += c.len().saturating_sub(max) as f64;
+                }
+            }
+        }
+        if let Some(min) = self.min_each_element {
+            for e in 0..8 {
+                if suppress_voids && e == VOID {
+                    continue;
+                }
+                total += min.saturating_sub(board.count(e)) as f64;
+            }
+        }
+        if self.no_opposed_adjacent {
+            total += board
+                .adjacent_pairs()
+                .filter(|(i, j)| board.tiles[*j].1 == opposite_element(board.tiles[*i].1))
+                .count() as f64;
+        }
+        for d in self.dealt_desires.iter() {
+            total += (self.min_desire_points - d.score(board)).max(0.0);
+        }
+        total
+    }
+}
+
+pub struct DealtBoard {
+    pub board: Board,
+    // how many shuffles were tried
+    pub attempts: usize,
+    // how many repair steps were needed after that, if any
+    pub repair_steps: usize,
+    pub satisfied: bool,
+}
+
+pub fn shuffled_land_tiles(
+    land_card_counts: &[usize],
+    suppress_voids: bool,
+    rng: &mut impl Rng,
+) -> Vec<ElementTag> {
+    let mut rng = RefCell::new(rng);
+    let mut r: Vec<usize> = land_card_counts
+        .iter()
+        .enumerate()
+        .flat_map(|(i, c)| {
+            (0..*c).map({
+                let mut rng = rng.borrow_mut();
+                move |_| {
+                    if (suppress_voids && i == 3) || rng.gen_bool(0.5) {
+                        i * 2
+                    } else {
+                        i * 2 + 1
+                    }
+                }
+            })
+        })
+        .collect();
+    r.shuffle(rng.get_mut());
+    r
+}
+
+// shuffles until the constraints are met. If they never are, repairs the closest shuffle by swapping lands around and flipping them over (which is always something the players could have done with the physical tiles).
+pub fn deal_board(
+    land_card_counts: &[usize],
+    radius: usize,
+    suppress_voids: bool,
+    constraints: &BoardConstraints,
+    rng: &mut impl Rng,
+) -> DealtBoard {
+    assert_eq!(
+        land_card_counts.len(),
+        4,
+        "a board is dealt from 4 land pairs, got counts {land_card_counts:?}"
+    );
+    let mut best: Option<(f64, Board)> = None;
+    let mut attempts = 0;
+    while attempts < constraints.max_attempts.max(1) {
+        attempts += 1;
+        let board = Board::hexagon(
+            radius,
+            &shuffled_land_tiles(land_card_counts, suppress_voids, rng),
+        );
+        let v = constraints.violation(&board, suppress_voids);
+        if v == 0.0 {
+            return DealtBoard {
+                board,
+                attempts,
+                repair_steps: 0,
+                satisfied: true,
+            };
+        }
+        if best.as_ref().is_none_or(|(bv, _)| v < *bv) {
+            best = Some((v, board));
+        }
+    }
+
+    let (mut current_violation, mut board) = best.unwrap();
+    let mut repair_steps = 0;
+    while current_violation > 0.0 && repair_steps < constraints.max_repair_steps {
+        repair_steps += 1;
+        let mut candidate = board.clone();
+        let n = candidate.tiles.len();
+        let i = rng.gen_range(0..n);
+        let e = candidate.tiles[i].1;
+        let flippable = !(suppress_voids && (e == TOMB || e == VOID));
+        if flippable && rng.gen_bool(0.5) {
+            candidate.tiles[i].1 = opposite_element(e);
+        } else {
+            let j = rng.gen_range(0..n);
+            let ej = candidate.tiles[j].1;
+            candidate.tiles[j].1 = e;
+            candidate.tiles[i].1 = ej;
+        }
+        let v = constraints.violation(&candidate, suppress_voids);
+        // sideways moves are accepted so that the search can wander across plateaus
+        if v <= current_violation {
+            current_violation = v;
+            board = candidate;
+        }
+    }
+    DealtBoard {
+        board,
+        attempts,
+        repair_steps,
+        satisfied: current_violation == 0.0,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AgentKind {
+    Guy,
+    Mage,
+    Dead,
+}
+
+pub struct BoardAgent {
+    pub at: Coord,
+    pub kind: AgentKind,
+    // index into TEAM_COLORS
+    pub team: usize,
+}
+impl BoardAgent {
+    // from the command line, "x,y:kind:team", eg "1,-2:mage:0"
+    pub fn parse(s: &str) -> Result<BoardAgent, String> {
+        let wrong = || format!("couldn't read the agent {s:?}, it should be like 1,-2:mage:0");
+        let mut fields = s.split(':');
+        let (x, y) = fields.next().and_then(|c| c.split_once(',')).ok_or_else(wrong)?;
+        let at = Coord::new(x.parse().map_err(|_| wrong())?, y.parse().map_err(|_| wrong())?);
+        let kind = match fields.next().ok_or_else(wrong)? {
+            "guy" => AgentKind::Guy,
+            "mage" => AgentKind::Mage,
+            "dead" => AgentKind::Dead,
+            k => return Err(format!("{k:?} isn't a kind of agent, there's guy, mage and dead")),
+        };
+        let team = fields.next().ok_or_else(wrong)?.parse().map_err(|_| wrong())?;
+        if fields.next().is_some() {
+            return Err(wrong());
+        }
+        Ok(BoardAgent { at, kind, team })
+    }
+}
+
+pub const TEAM_COLORS: [&str; 6] = ["e0584d", "4d8ee0", "e8c33a", "5fb56b", "a56ad6", "ef8f3c"];
+// the body fills of the guy2 assets, which get replaced with the team color
+pub const GUY2_BODY_COLOR: &str = "3f3f3f";
+pub const DEAD_GUY2_BODY_COLOR: &str = "5c5c5c";
+pub const HIGHLIGHT_COLOR: &str = "ffd23f";
+
+// extra things to draw over a board, for post-game review and for the figures in the manual
+#[derive(Default)]
+pub struct BoardAnnotations {
+    // axial coordinates under each tile
+    pub coordinates: bool,
+    pub agents: Vec<BoardAgent>,
+    // rings the tiles that earn this desire its points, and links the adjacent ones, so that clusters, chains and pairs read as shapes
+    pub highlight: Option<Desire>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a forest in the middle, ringed by field, field, lake, tomb, forest, mountain. Neighbours in the ring are adjacent, and all of them are adjacent to the middle.
+    fn small_board() -> Board {
+        Board::hexagon(1, &[FOREST, FIELD, FIELD, LAKE, TOMB, FOREST, MOUNTAIN])
+    }
+    fn selected(d: Desire) -> (f64, Vec<usize>) {
+        let s = d.evaluate(&small_board());
+        (s.points, dedup(s.tiles))
+    }
+
+    #[test]
+    fn desires_select_the_tiles_that_score() {
+        assert_eq!(selected(Desire::Each(FIELD, 2.0)), (4.0, vec![1, 2]));
+        assert_eq!(selected(Desire::LargestCluster(FOREST)), (2.0, vec![0, 5]));
+        assert_eq!(selected(Desire::Chain(FIELD, FOREST, LAKE)), (4.0, vec![0, 1, 2, 3]));
+        assert_eq!(selected(Desire::AdjacentPairs(LAKE, TOMB, 1.0)), (1.0, vec![3, 4]));
+        assert_eq!(selected(Desire::Isolated(TOMB, vec![FIELD], 1.0)), (1.0, vec![4]));
+        assert_eq!(selected(Desire::Isolated(LAKE, vec![FIELD], 1.0)), (0.0, vec![]));
+        // the forest is the road, with both fields and the lake on its banks
+        assert_eq!(selected(Desire::Banks(FIELD, FOREST, LAKE)), (2.0, vec![0, 1, 2, 3, 5]));
+        assert_eq!(selected(Desire::Exactly(FIELD, 3, 5.0)), (0.0, vec![]));
+        // scores without any tiles to point at
+        assert_eq!(selected(Desire::Absent(VOLCANO, 3.0)), (3.0, vec![]));
+    }
+
+    // repairs only swap lands around and flip them over, so each land pair keeps its count
+    fn pair_counts(board: &Board) -> Vec<usize> {
+        let mut r = vec![0; 4];
+        for (_, e) in board.tiles.iter() {
+            r[e / 2] += 1;
+        }
+        r
+    }
+
+    #[test]
+    fn repairs_boards_that_no_shuffle_satisfies() {
+        let constraints = BoardConstraints {
+            max_cluster_size: Some(1),
+            min_each_element: Some(1),
+            no_opposed_adjacent: true,
+            max_attempts: 1,
+            max_repair_steps: 20000,
+            ..BoardConstraints::default()
+        };
+        let cuts = [6, 5, 5, 3];
+        let deal = || deal_board(&cuts, 2, true, &constraints, &mut rand::rngs::StdRng::seed_from_u64(3));
+        let dealt = deal();
+        assert!(dealt.satisfied);
+        assert!(dealt.repair_steps > 0);
+        assert_eq!(constraints.violation(&dealt.board, true), 0.0);
+        assert_eq!(pair_counts(&dealt.board), cuts);
+        // tombs can't be flipped over into voids when voids are suppressed
+        assert_eq!(dealt.board.count(VOID), 0);
+        // the same seed deals the same board
+        assert_eq!(deal().board.tiles, dealt.board.tiles);
+    }
+
+    #[test]
+    fn gives_up_on_impossible_constraints() {
+        let constraints = BoardConstraints {
+            min_each_element: Some(3),
+            max_attempts: 5,
+            max_repair_steps: 300,
+            ..BoardConstraints::default()
+        };
+        let cuts = [2, 2, 2, 1];
+        let dealt = deal_board(&cuts, 1, false, &constraints, &mut rand::rngs::StdRng::seed_from_u64(0));
+        assert!(!dealt.satisfied);
+        assert_eq!((dealt.attempts, dealt.repair_steps), (5, 300));
+        assert_eq!(pair_counts(&dealt.board), cuts);
+    }
+
+    #[test]
+    fn parses_desires_and_agents() {
+        assert_eq!(Desire::parse("each:field:1.5"), Ok(Desire::Each(FIELD, 1.5)));
+        assert_eq!(
+            Desire::parse("isolated:tomb:field,forest:2"),
+            Ok(Desire::Isolated(TOMB, vec![FIELD, FOREST], 2.0))
+        );
+        assert_eq!(
+            Desire::parse("weighted:1,0,0,0,0,0,0,-1"),
+            Ok(Desire::Weighted([1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, -1.0]))
+        );
+        assert!(Desire::parse("weighted:1,2").is_err());
+        assert!(Desire::parse("chain:field:forest").is_err());
+        assert!(Desire::parse("each:frost:1").is_err());
+        let a = BoardAgent::parse("1,-2:mage:3").unwrap();
+        assert_eq!((a.at, a.kind, a.team), (Coord::new(1, -2), AgentKind::Mage, 3));
+        assert!(BoardAgent::parse("1,-2:wizard:3").is_err());
+        assert!(BoardAgent::parse("1:guy:0").is_err());
+    }
+}
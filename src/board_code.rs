@@ -0,0 +1,517 @@
+// short codes like "K7QA-2XM5" that capture everything that goes into dealing a board, so that a group can say "play board K7QA-2XM5" and everyone lays out the same tiles.
+// the code is a little bitstream written out in crockford's base32 (no I, L, O or U, so that it's hard to misread), followed by crockford's check character (the whole number mod 37, so it can also be one of *~$=U), grouped by dashes.
+// desire points (and the weights of Weighted desires) are stored to the nearest quarter, which covers every card we have.
+
+use super::*;
+
+// everything that generate_board needs besides the assets
+#[derive(Clone, Debug)]
+pub struct BoardParams {
+    pub weights: Vec<f64>,
+    pub radius: usize,
+    pub suppress_voids: bool,
+    pub seed: u64,
+    pub constraints: BoardConstraints,
+}
+
+const CODE_VERSION: u64 = 0;
+const BASE32: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+// the check character is mod 37, the base32 symbols and five more
+const CHECK_SYMBOLS: &[u8; 37] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ*~$=U";
+
+// far bigger than any table, it's only there so that a mistyped or made up code can't ask for a board that takes forever to deal
+pub const MAX_BOARD_RADIUS: usize = 16;
+// for the same reason, how much longer than the defaults a code may ask the search to try
+const MAX_SEARCH_FACTOR: usize = 10;
+// forest/field, mountain/volcano, lake/ice, tomb/void
+const LAND_PAIRS: usize = 4;
+
+pub fn hexagon_tile_count(radius: usize) -> usize {
+    1 + 3 * radius * (radius + 1)
+}
+
+impl BoardParams {
+    // the code stores the number of tiles per land pair rather than the weights, since that's all the weights are used for. Decoded params have those counts as their weights, which deal the same board.
+    pub fn code(&self) -> String {
+        let mut b = BitWriter::default();
+        b.bits(CODE_VERSION, 2);
+        b.flag(self.suppress_voids);
+        b.varint(self.radius as u64);
+        let cuts = generation::weights_to_cuts(&self.weights, hexagon_tile_count(self.radius));
+        b.varint(cuts.len() as u64 - 1);
+        // the last one is implied by the total
+        for c in cuts[..cuts.len() - 1].iter() {
+            b.varint(*c as u64);
+        }
+        b.varint(self.seed);
+        let c = &self.constraints;
+        let defaults = BoardConstraints::default();
+        let constrained = c.max_cluster_size.is_some()
+            || c.min_each_element.is_some()
+            || c.no_opposed_adjacent
+            || !c.dealt_desires.is_empty();
+        b.flag(constrained);
+        if constrained {
+            b.optional(c.max_cluster_size);
+            b.optional(c.min_each_element);
+            b.flag(c.no_opposed_adjacent);
+            b.varint(c.dealt_desires.len() as u64);
+            for d in c.dealt_desires.iter() {
+                b.desire(d);
+            }
+            if !c.dealt_desires.is_empty() {
+                b.quarters(c.min_desire_points);
+            }
+            let default_search = c.max_attempts == defaults.max_attempts
+                && c.max_repair_steps == defaults.max_repair_steps;
+            b.flag(!default_search);
+            if !default_search {
+                b.varint(c.max_attempts as u64);
+                b.varint(c.max_repair_steps as u64);
+            }
+        }
+
+        let symbols: Vec<u8> = b.into_symbols();
+        let check = CHECK_SYMBOLS[check_symbol(&symbols) as usize] as char;
+        let mut r = String::new();
+        for (i, ch) in symbols.iter().map(|s| BASE32[*s as usize] as char).chain(std::iter::once(check)).enumerate() {
+            if i != 0 && i % 4 == 0 {
+                r.push('-');
+            }
+            r.push(ch);
+        }
+        r
+    }
+
+    pub fn from_code(code: &str) -> Result<BoardParams, String> {
+        let mut chars: Vec<char> = code.chars().filter(|c| !matches!(c, '-' | ' ')).collect();
+        let Some(check) = chars.pop() else {
+            return Err(format!("board code {code:?} is empty"));
+        };
+        let not_a_code_character =
+            |ch: char| format!("board code {code:?} contains {ch:?}, which isn't a board code character");
+        let decode = |ch: char, alphabet: &[u8]| -> Result<u8, String> {
+            let c = match ch.to_ascii_uppercase() {
+                'O' => '0',
+                'I' | 'L' => '1',
+                c => c,
+            };
+            alphabet
+                .iter()
+                .position(|b| *b as char == c)
+                .map(|p| p as u8)
+                .ok_or_else(|| not_a_code_character(ch))
+        };
+        let symbols: Vec<u8> = chars.iter().map(|ch| decode(*ch, BASE32)).collect::<Result<_, _>>()?;
+        if decode(check, CHECK_SYMBOLS)? != check_symbol(&symbols) {
+            return Err(format!("board code {code:?} has a typo in it (its check character doesn't match)"));
+        }
+        let malformed = || format!("board code {code:?} is malformed");
+
+        let mut b = BitReader { symbols: &symbols, at: 0 };
+        let version = b.bits(2).ok_or_else(malformed)?;
+        if version != CODE_VERSION {
+            return Err(format!("board code {code:?} is from a different version of the generator"));
+        }
+        let suppress_voids = b.flag().ok_or_else(malformed)?;
+        let radius = b.varint().ok_or_else(malformed)?;
+        if radius > MAX_BOARD_RADIUS as u64 {
+            return Err(format!(
+                "board code {code:?} is for a board of radius {radius}, the most it can be is {MAX_BOARD_RADIUS}"
+            ));
+        }
+        let radius = radius as usize;
+        let total = hexagon_tile_count(radius);
+        // the last cut is implied
+        if b.varint().ok_or_else(malformed)? != LAND_PAIRS as u64 - 1 {
+            return Err(malformed());
+        }
+        let mut cuts = Vec::new();
+        let mut dealt: usize = 0;
+        for _ in 0..LAND_PAIRS - 1 {
+            let c = usize::try_from(b.varint().ok_or_else(malformed)?).map_err(|_| malformed())?;
+            dealt = dealt.checked_add(c).filter(|d| *d <= total).ok_or_else(malformed)?;
+            cuts.push(c);
+        }
+        let last = total - dealt;
+        cuts.push(last);
+        let seed = b.varint().ok_or_else(malformed)?;
+        let mut constraints = BoardConstraints::default();
+        if b.flag().ok_or_else(malformed)? {
+            constraints.max_cluster_size = b.optional().ok_or_else(malformed)?;
+            constraints.min_each_element = b.optional().ok_or_else(malformed)?;
+            constraints.no_opposed_adjacent = b.flag().ok_or_else(malformed)?;
+            let desire_count = b.varint().ok_or_else(malformed)?;
+            for _ in 0..desire_count {
+                constraints
+                    .dealt_desires
+                    .push(b.desire().ok_or_else(malformed)?);
+            }
+            if !constraints.dealt_desires.is_empty() {
+                constraints.min_desire_points = b.quarters().ok_or_else(malformed)?;
+            }
+            if b.flag().ok_or_else(malformed)? {
+                let defaults = BoardConstraints::default();
+                let mut search = |default: usize, what: &str| -> Result<usize, String> {
+                    let v = b.varint().ok_or_else(malformed)?;
+                    let max = default * MAX_SEARCH_FACTOR;
+                    if v > max as u64 {
+                        return Err(format!("board code {code:?} asks for {v} {what}, the most it can ask for is {max}"));
+                    }
+                    Ok(v as usize)
+                };
+                constraints.max_attempts = search(defaults.max_attempts, "shuffles")?;
+                constraints.max_repair_steps = search(defaults.max_repair_steps, "repair steps")?;
+            }
+        }
+        if !b.rest_is_padding() {
+            return Err(malformed());
+        }
+        Ok(BoardParams {
+            weights: cuts.into_iter().map(|c| c as f64).collect(),
+            radius,
+            suppress_voids,
+            seed,
+            constraints,
+        })
+    }
+}
+
+// the symbols read as one big base32 number, mod 37. 37 is prime and doesn't divide any difference a single wrong character or a swap of two neighbours can make, so those are always caught.
+fn check_symbol(symbols: &[u8]) -> u8 {
+    symbols.iter().fold(0, |r, s| (r * 32 + *s as u32) % 37) as u8
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bits: Vec<bool>,
+}
+impl BitWriter {
+    fn bits(&mut self, v: u64, n: usize) {
+        for i in (0..n).rev() {
+            self.bits.push((v >> i) & 1 == 1);
+        }
+    }
+    fn flag(&mut self, v: bool) {
+        self.bits.push(v);
+    }
+    // four bits at a time, each group preceded by whether there's another after it
+    fn varint(&mut self, mut v: u64) {
+        loop {
+            let more = v >= 16;
+            self.flag(more);
+            self.bits(v & 15, 4);
+            v >>= 4;
+            if !more {
+                break;
+            }
+        }
+    }
+    fn optional(&mut self, v: Option<usize>) {
+        self.flag(v.is_some());
+        if let Some(v) = v {
+            self.varint(v as u64);
+        }
+    }
+    fn quarters(&mut self, v: f64) {
+        let q = (v * 4.0).round() as i64;
+        // zigzag, so that small negatives stay short
+        self.varint(((q << 1) ^ (q >> 63)) as u64);
+    }
+    fn element(&mut self, e: ElementTag) {
+        self.bits(e as u64, 3);
+    }
+    fn desire(&mut self, d: &Desire) {
+        match d {
+            Desire::Each(e, p) => {
+                self.bits(0, 4);
+                self.element(*e);
+                self.quarters(*p);
+            }
+            Desire::AdjacentPairs(a, b, p) => {
+                self.bits(1, 4);
+                self.element(*a);
+                self.element(*b);
+                self.quarters(*p);
+            }
+            Desire::Exactly(e, n, p) => {
+                self.bits(2, 4);
+                self.element(*e);
+                self.varint(*n as u64);
+                self.quarters(*p);
+            }
+            Desire::Chain(a, b, c) => {
+                self.bits(3, 4);
+                self.element(*a);
+                self.element(*b);
+                self.element(*c);
+            }
+            Desire::Triangle(a, b, c, p) => {
+                self.bits(4, 4);
+                self.element(*a);
+                self.element(*b);
+                self.element(*c);
+                self.quarters(*p);
+            }
+            Desire::LargestCluster(e) => {
+                self.bits(5, 4);
+                self.element(*e);
+            }
+            Desire::Absent(e, p) => {
+                self.bits(6, 4);
+                self.element(*e);
+                self.quarters(*p);
+            }
+            Desire::NeverAdjacent(a, b, p) => {
+                self.bits(7, 4);
+                self.element(*a);
+                self.element(*b);
+                self.quarters(*p);
+            }
+            Desire::Isolated(e, others, p) => {
+                self.bits(8, 4);
+                self.element(*e);
+                self.varint(others.len() as u64);
+                for o in others.iter() {
+                    self.element(*o);
+                }
+                self.quarters(*p);
+            }
+            Desire::LargestPatch(a, b) => {
+                self.bits(9, 4);
+                self.element(*a);
+                self.element(*b);
+            }
+            Desire::Banks(a, road, b) => {
+                self.bits(10, 4);
+                self.element(*a);
+                self.element(*road);
+                self.element(*b);
+            }
+            Desire::Weighted(ws) => {
+                self.bits(11, 4);
+                for w in ws.iter() {
+                    self.quarters(*w);
+                }
+            }
+        }
+    }
+    fn into_symbols(self) -> Vec<u8> {
+        self.bits
+            .chunks(5)
+            .map(|c| {
+                let mut s = 0;
+                for i in 0..5 {
+                    s = (s << 1) | (c.get(i).copied().unwrap_or(false) as u8);
+                }
+                s
+            })
+            .collect()
+    }
+}
+
+struct BitReader<'a> {
+    symbols: &'a [u8],
+    at: usize,
+}
+impl BitReader<'_> {
+    fn bit(&mut self) -> Option<bool> {
+        let s = self.symbols.get(self.at / 5)?;
+        let r = (s >> (4 - self.at % 5)) & 1 == 1;
+        self.at += 1;
+        Some(r)
+    }
+    fn bits(&mut self, n: usize) -> Option<u64> {
+        let mut r = 0;
+        for _ in 0..n {
+            r = (r << 1) | self.bit()? as u64;
+        }
+        Some(r)
+    }
+    fn flag(&mut self) -> Option<bool> {
+        self.bit()
+    }
+    fn varint(&mut self) -> Option<u64> {
+        let mut r: u64 = 0;
+        let mut shift = 0;
+        loop {
+            let more = self.flag()?;
+            if shift >= 64 {
+                return None;
+            }
+            r |= self.bits(4)? << shift;
+            shift += 4;
+            if !more {
+                return Some(r);
+            }
+        }
+    }
+    fn optional(&mut self) -> Option<Option<usize>> {
+        Some(if self.flag()? {
+            Some(self.varint()? as usize)
+        } else {
+            None
+        })
+    }
+    fn quarters(&mut self) -> Option<f64> {
+        let z = self.varint()?;
+        let q = ((z >> 1) as i64) ^ -((z & 1) as i64);
+        Some(q as f64 / 4.0)
+    }
+    fn element(&mut self) -> Option<ElementTag> {
+        Some(self.bits(3)? as ElementTag)
+    }
+    fn desire(&mut self) -> Option<Desire> {
+        Some(match self.bits(4)? {
+            0 => Desire::Each(self.element()?, self.quarters()?),
+            1 => Desire::AdjacentPairs(self.element()?, self.element()?, self.quarters()?),
+            2 => Desire::Exactly(self.element()?, self.varint()? as usize, self.quarters()?),
+            3 => Desire::Chain(self.element()?, self.element()?, self.element()?),
+            4 => Desire::Triangle(
+                self.element()?,
+                self.element()?,
+                self.element()?,
+                self.quarters()?,
+            ),
+            5 => Desire::LargestCluster(self.element()?),
+            6 => Desire::Absent(self.element()?, self.quarters()?),
+            7 => Desire::NeverAdjacent(self.element()?, self.element()?, self.quarters()?),
+            8 => {
+                let e = self.element()?;
+                let n = self.varint()?;
+                let mut others = Vec::new();
+                for _ in 0..n {
+                    others.push(self.element()?);
+                }
+                Desire::Isolated(e, others, self.quarters()?)
+            }
+            9 => Desire::LargestPatch(self.element()?, self.element()?),
+            10 => Desire::Banks(self.element()?, self.element()?, self.element()?),
+            11 => {
+                let mut ws = [0.0; 8];
+                for w in ws.iter_mut() {
+                    *w = self.quarters()?;
+                }
+                Desire::Weighted(ws)
+            }
+            _ => return None,
+        })
+    }
+    // the last symbol may have been filled out with zeroes
+    fn rest_is_padding(&mut self) -> bool {
+        let end = self.symbols.len() * 5;
+        self.at + 5 > end && (self.at..end).all(|_| self.bit() == Some(false))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> BoardParams {
+        BoardParams {
+            weights: vec![12.7, 7.0, 6.0, 5.0],
+            radius: 3,
+            suppress_voids: true,
+            seed: 1234567,
+            constraints: BoardConstraints {
+                max_cluster_size: Some(4),
+                min_each_element: Some(1),
+                dealt_desires: vec![Desire::Each(2, 1.5), Desire::Chain(0, 3, 5)],
+                min_desire_points: -0.75,
+                ..BoardConstraints::default()
+            },
+        }
+    }
+
+    #[test]
+    fn codes_round_trip() {
+        let params = example();
+        let code = params.code();
+        let decoded = BoardParams::from_code(&code).unwrap();
+        assert_eq!(decoded.code(), code);
+        assert_eq!(decoded.radius, params.radius);
+        assert_eq!(decoded.seed, params.seed);
+        assert_eq!(
+            decoded.weights,
+            generation::weights_to_cuts(&params.weights, hexagon_tile_count(params.radius))
+                .into_iter()
+                .map(|c| c as f64)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(decoded.constraints.min_desire_points, -0.75);
+        // lowercase, and the characters crockford's base32 leaves out read as the ones they look like
+        assert!(BoardParams::from_code(&code.to_lowercase()).is_ok());
+    }
+
+    // a code with a valid check character for whatever's been written
+    fn code_of(b: BitWriter) -> String {
+        let symbols = b.into_symbols();
+        let check = CHECK_SYMBOLS[check_symbol(&symbols) as usize] as char;
+        symbols.iter().map(|s| BASE32[*s as usize] as char).chain(std::iter::once(check)).collect()
+    }
+
+    #[test]
+    fn huge_boards_are_refused() {
+        let params = BoardParams { radius: MAX_BOARD_RADIUS + 1, ..example() };
+        assert!(BoardParams::from_code(&params.code()).is_err());
+        let params = BoardParams { radius: MAX_BOARD_RADIUS, ..example() };
+        assert!(BoardParams::from_code(&params.code()).is_ok());
+
+        let mut b = BitWriter::default();
+        b.bits(CODE_VERSION, 2);
+        b.flag(true);
+        b.varint(u64::MAX);
+        assert!(BoardParams::from_code(&code_of(b)).is_err());
+        // cuts that overflow when they're added up
+        let mut b = BitWriter::default();
+        b.bits(CODE_VERSION, 2);
+        b.flag(true);
+        b.varint(3);
+        b.varint(2);
+        b.varint(u64::MAX);
+        b.varint(u64::MAX);
+        b.varint(0);
+        b.flag(false);
+        assert!(BoardParams::from_code(&code_of(b)).is_err());
+    }
+
+    #[test]
+    fn malformed_codes_are_refused() {
+        // six cuts instead of three
+        assert!(BoardParams::from_code("44M4444404").is_err());
+        // shuffles and repair steps of 2^60
+        assert!(BoardParams::from_code("44C8882S1G6222222222222220622222222222222045").is_err());
+        let mut params = example();
+        params.constraints.max_attempts = BoardConstraints::default().max_attempts * MAX_SEARCH_FACTOR;
+        params.constraints.max_repair_steps = 1;
+        assert!(BoardParams::from_code(&params.code()).is_ok());
+        params.constraints.max_attempts += 1;
+        assert!(BoardParams::from_code(&params.code()).is_err());
+    }
+
+    #[test]
+    fn typos_are_caught() {
+        let code: Vec<char> = example().code().chars().filter(|c| *c != '-').collect();
+        for i in 0..code.len() {
+            for c in CHECK_SYMBOLS.iter().map(|c| *c as char) {
+                if c == code[i] {
+                    continue;
+                }
+                let mut typo = code.clone();
+                typo[i] = c;
+                let typo: String = typo.into_iter().collect();
+                assert!(BoardParams::from_code(&typo).is_err(), "{typo} was accepted");
+            }
+        }
+        for i in 0..code.len() - 2 {
+            if code[i] == code[i + 1] {
+                continue;
+            }
+            let mut swapped = code.clone();
+            swapped.swap(i, i + 1);
+            let swapped: String = swapped.into_iter().collect();
+            assert!(BoardParams::from_code(&swapped).is_err(), "{swapped} was accepted");
+        }
+    }
+}
@@ -3,7 +3,7 @@
 use elementtree::WriteOptions;
 use mako_infinite_shuffle::{Cross, Indexing};
 use nalgebra::{Rotation2, Vector2};
-use std::{cell::RefCell, f64::consts::TAU, fmt::Display, fs::File, io::Write, iter, path::Path, rc::Rc};
+use std::{cell::{Cell, OnceCell, RefCell}, collections::{HashMap, HashSet}, f64::consts::TAU, fmt::Display, fs::File, io::Write, iter, ops::Range, path::{Path, PathBuf}, rc::Rc};
 
 pub fn from_angle_mag(angle: f64, mag: f64) -> V2 {
     V2::new(angle.cos() * mag, angle.sin() * mag)
@@ -49,7 +49,10 @@ where
     }
 }
 
-const CARD_BACKGROUND_COLOR: &'static str = "f1f2f2";
+pub const CARD_BACKGROUND_COLOR: &str = "f1f2f2";
+// the background polygons are 144 by 198, this scales them up to cover the card
+pub const CARD_BACKGROUND_TRANSFORM: Transform =
+    Transform::Matrix([1.1024306, 0.0, 0.0, 1.1024306, 0.0, 2e-4]);
 
 // field forest mountain volcano lake ice tomb void
 pub type ElementTag = usize;
@@ -110,27 +113,21 @@ pub fn pair_name_for(e: ElementTag) -> &'static str {
 pub struct LandTheme {
     colors_back: [&'static str; 8],
     color_front: [&'static str; 8],
-    asset_paths: [&'static str; 8],
+    // looked up in the asset registry, so an overlay can replace them
+    asset_names: [&'static str; 8],
 }
 // we need to be able to const initialize it twice for it to be the initial value of LAND_THEME, clone isn't const
 macro_rules! mako_land_theme {
     ()=> {
         LandTheme {
             colors_back: [
-                "b5efb9", "94cf9c", "eeeca7", "efcfcf", "c3edf1", "e1eff0", "ebebeb", "969696",
+                "b5efb9", "94cf9c", "eeeca7", "ecd2d2", "89e5ee", "e5f5f6", "b5b5b5", "969696",
             ],
             color_front: [
-                "a3e2a7", "7eb47f", "e5e383", "f2b7b7", "a5dae0", "f4fcfd", "dedede", "414141",
+                "a3e2a7", "7eb47f", "e5e383", "ecbdbd", "6fced9", "fcffff", "a8a8a8", "414141",
             ],
-            asset_paths: [
-                "assets/field.svg",
-                "assets/forest.svg",
-                "assets/mountain.svg",
-                "assets/volcano.svg",
-                "assets/lake.svg",
-                "assets/ice.svg",
-                "assets/tomb.svg",
-                "assets/void.svg",
+            asset_names: [
+                "field", "forest", "mountain", "volcano", "lake", "ice", "tomb", "void",
             ]
         }
     }
@@ -224,20 +221,83 @@ pub type R2 = Rotation2<f64>;
 // pub const end_graphic_allowable_rad:f64 = 262.5;
 
 // this was for the rounded guy
-// pub const GUY2_ANCHOR: V2 = V2::new(19.912, 45.846);
-pub const GUY2_ANCHOR: V2 = V2::new(21.243, 61.013);
-pub const GUY2_DEAD_ANCHOR: V2 = V2::new(28.7, 47.095);
-pub const GUY2_MAGE_ANCHOR: V2 = V2::new(36.380, 66.810);
 pub const GUY2_RISEN_ANCHOR: V2 = V2::new(20.2, 45.75);
 pub const GUY2_RAD: f64 = 20.2;
 pub const GUY2_ADJACENCY_SMALLERNESS: f64 = 0.7;
 pub const BIG_ELEMENT_SPAN: f64 = 107.299;
 pub const BIG_ELEMENT_DIMENSIONS: V2 = V2::new(BIG_ELEMENT_SPAN, BIG_ELEMENT_SPAN);
 pub const BIG_ELEMENT_RAD: f64 = BIG_ELEMENT_SPAN / 2.0;
-pub const END_GRAPHIC_CENTER: V2 = V2::new(79.375, 138.906);
-pub const GRAPHIC_RAD: f64 = 69.4535;
-pub const CARD_DIMENSIONS: V2 = V2::new(158.75, 218.28127);
-pub const CUTLINE_INSET: V2 = V2::new(9.922, 9.922);
+
+// the size of card a deck is printed on. Every format is laid out at the same width as a mini card, 158.75 design units including the bleed, so that nothing drawn across the card has to change, the format decides how tall the layout is, where the cutline is, and how big it's printed. So a poker card is laid out like a slightly taller mini card, then printed larger.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CardFormat {
+    pub name: &'static str,
+    // within the cutline, in inches
+    pub cut: V2,
+}
+pub const MINI_CARDS: CardFormat = CardFormat { name: "mini", cut: V2::new(1.75, 2.5) };
+pub const POKER_CARDS: CardFormat = CardFormat { name: "poker", cut: V2::new(2.5, 3.5) };
+pub const BRIDGE_CARDS: CardFormat = CardFormat { name: "bridge", cut: V2::new(2.25, 3.5) };
+pub const TAROT_CARDS: CardFormat = CardFormat { name: "tarot", cut: V2::new(2.75, 4.75) };
+pub const SQUARE_CARDS: CardFormat = CardFormat { name: "square", cut: V2::new(2.5, 2.5) };
+pub const CARD_FORMATS: [CardFormat; 5] = [MINI_CARDS, POKER_CARDS, BRIDGE_CARDS, TAROT_CARDS, SQUARE_CARDS];
+// the cards are drawn with this much bleed around the cutline already, and print vendors ask for at least this much
+pub const BLEED_INCHES: f64 = 1.0 / 8.0;
+impl CardFormat {
+    pub fn by_name(name: &str) -> Option<CardFormat> {
+        CARD_FORMATS.iter().copied().find(|f| f.name == name)
+    }
+    // layout units per inch
+    fn layout_scale(&self) -> f64 {
+        let mini_width = MINI_CARDS.cut.x + 2.0 * BLEED_INCHES;
+        mini_width * DESIGN_UNITS_PER_INCH / (self.cut.x + 2.0 * BLEED_INCHES)
+    }
+    // printed design units per layout unit
+    pub fn print_scale(&self) -> f64 {
+        DESIGN_UNITS_PER_INCH / self.layout_scale()
+    }
+    // the card, bleed and all, in layout units
+    pub fn dimensions(&self) -> V2 {
+        (self.cut + both_dims(2.0 * BLEED_INCHES)) * self.layout_scale()
+    }
+    pub fn cutline_inset(&self) -> V2 {
+        both_dims(BLEED_INCHES * self.layout_scale())
+    }
+    // the size of the card's svg
+    pub fn print_dimensions(&self) -> V2 {
+        self.dimensions() * self.print_scale()
+    }
+}
+thread_local! {
+    static CARD_FORMAT: Cell<CardFormat> = const { Cell::new(MINI_CARDS) };
+}
+// like the card print conf, this is global state rather than a parameter threaded through every generator. Set it before generating.
+pub fn set_card_format(format: CardFormat) {
+    CARD_FORMAT.with(|c| c.set(format));
+}
+pub fn card_format() -> CardFormat {
+    CARD_FORMAT.with(|c| c.get())
+}
+pub fn card_dimensions() -> V2 {
+    card_format().dimensions()
+}
+pub fn cutline_inset() -> V2 {
+    card_format().cutline_inset()
+}
+// the end cards' graphic is a circle as wide as the cut, sitting on the bottom of it
+pub fn graphic_rad() -> f64 {
+    cutline_bounds().width() / 2.0
+}
+pub fn end_graphic_center() -> V2 {
+    let cut = cutline_bounds();
+    V2::new(cut.center().x, cut.br.y - graphic_rad())
+}
+// how much taller the layout is than a mini card's, for things that are drawn relative to the bottom of the card
+pub fn extra_layout_height() -> f64 {
+    card_dimensions().y - MINI_CARDS.dimensions().y
+}
+// text and anything else that matters should stay this far inside the cutline, in case the cut drifts (1/8in, in printed design units)
+pub const SAFE_ZONE_INSET: f64 = 9.922;
 pub const STANDARD_PAIR_SCALE: f64 = 0.6;
 
 type Gravity = V2;
@@ -339,6 +399,7 @@ pub fn field_g(center: V2, scale: f64, to: &mut dyn Write) {
 "#,
         offset.x, offset.y, scale
     ).unwrap();
+    element_mark(FIELD, center, scale * BIG_ELEMENT_RAD, 0.0, to);
 }
 pub fn forest_g(center: V2, scale: f64, to: &mut dyn Write) {
     let offset = center - scale * BIG_ELEMENT_DIMENSIONS / 2.0;
@@ -348,7 +409,6 @@ pub fn forest_g(center: V2, scale: f64, to: &mut dyn Write) {
         r#"<g transform="translate({},{}) scale({})"><g
      inkscape:label="Layer 1"
      inkscape:groupmode="layer"
-     id="layer1"
      transform="translate(-1063.4417,-346.64583)"><g
        id="g495"
        transform="matrix(0,-2.3578005,2.3578005,0,-17505.41,5241.7665)"><circle
@@ -381,27 +441,29 @@ pub fn forest_g(center: V2, scale: f64, to: &mut dyn Write) {
            sodipodi:nodetypes="cc" /></g></g></g></g>
 "#,
         offset.x, offset.y, scale
-    ).unwrap()
+    ).unwrap();
+    element_mark(FOREST, center, scale * BIG_ELEMENT_RAD, 0.0, to);
 }
 pub fn volcano_g(center: V2, scale: f64, to: &mut dyn Write) {
     let offset = center - scale * BIG_ELEMENT_DIMENSIONS / 2.0;
+    let color_back = element_color_back(VOLCANO);
+    let color_front = element_color_front(VOLCANO);
     write!(to,
         r#"<g transform="translate({},{}) scale({})"><g
      inkscape:label="Layer 1"
      inkscape:groupmode="layer"
-     id="layer1"
      transform="translate(-1066.7783,-589.34825)"><g
        id="g481"
        transform="matrix(0,-2.3578005,2.3578005,0,-17789.482,5484.4689)"
        inkscape:export-filename="cardgen/lava.svg"
        inkscape:export-xdpi="96"
        inkscape:export-ydpi="96"><circle
-         style="fill:#efcfcf;fill-opacity:1;stroke-width:6.00001;stroke-linecap:round;stroke-linejoin:round"
+         style="fill:#{color_back};fill-opacity:1;stroke-width:6.00001;stroke-linecap:round;stroke-linejoin:round"
          id="circle480"
          cx="2053.3845"
          cy="8020.1484"
          r="22.754089" /><rect
-         style="fill:#f2b7b7;fill-opacity:1;stroke:none;stroke-width:6.00001;stroke-linecap:round;stroke-linejoin:round;stroke-opacity:1"
+         style="fill:#{color_front};fill-opacity:1;stroke:none;stroke-width:6.00001;stroke-linecap:round;stroke-linejoin:round;stroke-opacity:1"
          id="rect480"
          width="23.57588"
          height="23.57588"
@@ -410,7 +472,7 @@ pub fn volcano_g(center: V2, scale: f64, to: &mut dyn Write) {
          transform="rotate(-135)"
          rx="3.2182515"
          ry="3.2182515" /><circle
-         style="fill:#efcfcf;fill-opacity:1;stroke:none;stroke-width:5.77001;stroke-linecap:butt;stroke-linejoin:round;stroke-dasharray:none;stroke-opacity:1"
+         style="fill:#{color_back};fill-opacity:1;stroke:none;stroke-width:5.77001;stroke-linecap:butt;stroke-linejoin:round;stroke-dasharray:none;stroke-opacity:1"
          id="circle481"
          cx="8020.1484"
          cy="-2053.3845"
@@ -418,7 +480,8 @@ pub fn volcano_g(center: V2, scale: f64, to: &mut dyn Write) {
          transform="rotate(90)" /></g></g></g>
 "#,
         offset.x, offset.y, scale
-    ).unwrap()
+    ).unwrap();
+    element_mark(VOLCANO, center, scale * BIG_ELEMENT_RAD, 0.0, to);
 }
 pub fn mountain_g(center: V2, scale: f64, to: &mut dyn Write) {
     let offset = center - scale * BIG_ELEMENT_DIMENSIONS / 2.0;
@@ -426,7 +489,6 @@ pub fn mountain_g(center: V2, scale: f64, to: &mut dyn Write) {
         r#"<g transform="translate({},{}) scale({})"><g
      inkscape:label="Layer 1"
      inkscape:groupmode="layer"
-     id="layer1"
      transform="translate(-1176.4665,-652.1566)"><g
        id="g483"
        transform="matrix(0,-2.3578005,2.3578005,0,-17679.794,6022.1133)"
@@ -449,19 +511,20 @@ pub fn mountain_g(center: V2, scale: f64, to: &mut dyn Write) {
          ry="3.2182515" /></g></g></g>
 "#,
         offset.x, offset.y, scale
-    ).unwrap()
+    ).unwrap();
+    element_mark(MOUNTAIN, center, scale * BIG_ELEMENT_RAD, 0.0, to);
 }
 pub fn lake_g(center: V2, scale: f64, to: &mut dyn Write) {
     let offset = center - scale * BIG_ELEMENT_DIMENSIONS / 2.0;
+    let color_back = element_color_back(LAKE);
     write!(to,
         r#"<g transform="translate({},{}) scale({})"><g
      inkscape:label="Layer 1"
      inkscape:groupmode="layer"
-     id="layer1"
      transform="translate(-1176.4647,-526.5457)"><g
        id="g482"
        transform="matrix(0,-2.3578005,2.3578005,0,-17526.374,5896.5024)"><circle
-         style="fill:#c3edf1;fill-opacity:1;stroke-width:6.00001;stroke-linecap:round;stroke-linejoin:round"
+         style="fill:#{color_back};fill-opacity:1;stroke-width:6.00001;stroke-linecap:round;stroke-linejoin:round"
          id="circle482"
          cx="2254.7739"
          cy="7955.0786"
@@ -476,19 +539,21 @@ pub fn lake_g(center: V2, scale: f64, to: &mut dyn Write) {
          inkscape:export-ydpi="588.79651" /></g></g></g>
 "#,
         offset.x, offset.y, scale
-    ).unwrap()
+    ).unwrap();
+    element_mark(LAKE, center, scale * BIG_ELEMENT_RAD, 0.0, to);
 }
 pub fn ice_g(center: V2, scale: f64, to: &mut dyn Write) {
     let offset = center - scale * BIG_ELEMENT_DIMENSIONS / 2.0;
+    let color_back = element_color_back(ICE);
+    let color_front = element_color_front(ICE);
     write!(to,
         r#"<g transform="translate({},{}) scale({})"><g
      inkscape:label="Layer 1"
      inkscape:groupmode="layer"
-     id="layer1"
      transform="translate(-1060.7978,-472.41335)"><g
        id="g496"
        transform="matrix(0,-2.3578005,2.3578005,0,-17642.041,5367.534)"><circle
-         style="fill:#e1eff0;fill-opacity:1;stroke-width:6.00001;stroke-linecap:round;stroke-linejoin:round"
+         style="fill:#{color_back};fill-opacity:1;stroke-width:6.00001;stroke-linecap:round;stroke-linejoin:round"
          id="circle495"
          cx="2053.3845"
          cy="7955.0786"
@@ -496,14 +561,15 @@ pub fn ice_g(center: V2, scale: f64, to: &mut dyn Write) {
          sodipodi:nodetypes="cc"
          inkscape:connector-curvature="0"
          d="m 2053.3845,7966.9504 v -23.7436"
-         style="fill:none;fill-opacity:1;stroke:#f4fcfd;stroke-width:6.32004;stroke-linecap:round;stroke-linejoin:round;stroke-miterlimit:4;stroke-dasharray:none;stroke-opacity:1"
+         style="fill:none;fill-opacity:1;stroke:#{color_front};stroke-width:6.32004;stroke-linecap:round;stroke-linejoin:round;stroke-miterlimit:4;stroke-dasharray:none;stroke-opacity:1"
          id="path495"
          inkscape:export-filename="/home/mako/work/witching lands/land 2021/ice.png"
          inkscape:export-xdpi="588.79651"
          inkscape:export-ydpi="588.79651" /></g></g></g>
 "#,
         offset.x, offset.y, scale
-    ).unwrap()
+    ).unwrap();
+    element_mark(ICE, center, scale * BIG_ELEMENT_RAD, 0.0, to);
 }
 pub fn void_g(center: V2, scale: f64, to: &mut dyn Write) {
     let offset = center - scale * BIG_ELEMENT_DIMENSIONS / 2.0;
@@ -511,7 +577,6 @@ pub fn void_g(center: V2, scale: f64, to: &mut dyn Write) {
         r#"<g transform="translate({},{}) scale({})"><g
      inkscape:label="Layer 1"
      inkscape:groupmode="layer"
-     id="layer1"
      transform="translate(-1066.778,-714.96415)"><g
        id="g471"
        transform="matrix(0,-2.3578005,2.3578005,0,-17943.862,5610.0848)"><circle
@@ -531,26 +596,28 @@ pub fn void_g(center: V2, scale: f64, to: &mut dyn Write) {
          inkscape:export-ydpi="588.79651" /></g></g></g>
 "#,
         offset.x, offset.y, scale
-    ).unwrap()
+    ).unwrap();
+    element_mark(VOID, center, scale * BIG_ELEMENT_RAD, 0.0, to);
 }
 pub fn tomb_g(center: V2, scale: f64, to: &mut dyn Write) {
     let offset = center - scale * BIG_ELEMENT_DIMENSIONS / 2.0;
+    let color_back = element_color_back(TOMB);
+    let color_front = element_color_front(TOMB);
     write!(to,
         r#"<g transform="translate({},{}) scale({})"><g
      inkscape:label="Layer 1"
      inkscape:groupmode="layer"
-     id="layer1"
      transform="translate(-1176.4649,-777.7725)"><g
        id="g470"
        transform="matrix(0,-2.3578005,2.3578005,0,-17834.175,6147.7292)"><circle
-         style="fill:#ebebeb;fill-opacity:1;stroke-width:6.00001;stroke-linecap:round;stroke-linejoin:round"
+         style="fill:#{color_back};fill-opacity:1;stroke-width:6.00001;stroke-linecap:round;stroke-linejoin:round"
          id="circle464"
          cx="2254.7739"
          cy="8085.6245"
          r="22.754089" /><g
          id="g469"
          transform="translate(70.633624,-0.54262281)"><rect
-           style="fill:#ebebeb;fill-opacity:1;stroke:#dedede;stroke-width:5.77;stroke-linecap:round;stroke-linejoin:round;stroke-dasharray:none;stroke-opacity:1"
+           style="fill:#{color_back};fill-opacity:1;stroke:#{color_front};stroke-width:5.77;stroke-linecap:round;stroke-linejoin:round;stroke-dasharray:none;stroke-opacity:1"
            id="rect464"
            width="29.135572"
            height="29.13588"
@@ -559,7 +626,7 @@ pub fn tomb_g(center: V2, scale: f64, to: &mut dyn Write) {
            rx="14.567786"
            ry="14.567782"
            transform="rotate(135)" /><rect
-           style="fill:none;fill-opacity:1;stroke:#dedede;stroke-width:5.76998;stroke-linecap:round;stroke-linejoin:round;stroke-dasharray:none;stroke-opacity:1"
+           style="fill:none;fill-opacity:1;stroke:#{color_front};stroke-width:5.76998;stroke-linecap:round;stroke-linejoin:round;stroke-dasharray:none;stroke-opacity:1"
            id="rect465"
            width="10.431372"
            height="10.431509"
@@ -571,73 +638,83 @@ pub fn tomb_g(center: V2, scale: f64, to: &mut dyn Write) {
            id="g466"
            transform="matrix(-0.90017108,0,0,-0.90017108,4291.5704,14929.953)"
            style="stroke-width:1.11089"><path
-             style="fill:#969696;fill-opacity:1;stroke:#dedede;stroke-width:6.4099;stroke-linecap:butt;stroke-linejoin:round;stroke-dasharray:none;stroke-opacity:1"
+             style="fill:#969696;fill-opacity:1;stroke:#{color_front};stroke-width:6.4099;stroke-linecap:butt;stroke-linejoin:round;stroke-dasharray:none;stroke-opacity:1"
              d="m 2341.078,7586.8044 v 10.4299"
              id="path465" /><path
-             style="fill:#969696;fill-opacity:1;stroke:#dedede;stroke-width:6.4099;stroke-linecap:butt;stroke-linejoin:round;stroke-dasharray:none;stroke-opacity:1"
+             style="fill:#969696;fill-opacity:1;stroke:#{color_front};stroke-width:6.4099;stroke-linecap:butt;stroke-linejoin:round;stroke-dasharray:none;stroke-opacity:1"
              d="m 2341.2104,7608.2873 v 10.4299"
              id="path466" /></g><g
            id="g468"
            transform="matrix(0,0.90017108,0.90017108,0,-4659.6456,5978.7373)"
-           style="fill:#dedede;fill-opacity:1;stroke-width:1.11089"><path
-             style="fill:#dedede;fill-opacity:1;stroke:#dedede;stroke-width:6.4099;stroke-linecap:butt;stroke-linejoin:round;stroke-dasharray:none;stroke-opacity:1"
+           style="fill:#{color_front};fill-opacity:1;stroke-width:1.11089"><path
+             style="fill:#{color_front};fill-opacity:1;stroke:#{color_front};stroke-width:6.4099;stroke-linecap:butt;stroke-linejoin:round;stroke-dasharray:none;stroke-opacity:1"
              d="m 2341.078,7586.8044 v 10.4299"
              id="path467" /><path
-             style="fill:#dedede;fill-opacity:1;stroke:#dedede;stroke-width:6.4099;stroke-linecap:butt;stroke-linejoin:round;stroke-dasharray:none;stroke-opacity:1"
+             style="fill:#{color_front};fill-opacity:1;stroke:#{color_front};stroke-width:6.4099;stroke-linecap:butt;stroke-linejoin:round;stroke-dasharray:none;stroke-opacity:1"
              d="m 2341.2104,7608.2873 v 10.4299"
              id="path468" /></g></g></g></g></g>
 "#,
         offset.x, offset.y, scale
     ).unwrap();
+    element_mark(TOMB, center, scale * BIG_ELEMENT_RAD, 0.0, to);
 }
 
 pub fn end_front_inner(inserting: &impl Display, scores: String, to: &mut dyn Write) {
     let number_offset = if &scores == "1" { 74.8 } else { 79.000023 };
-    write!(to,
-r##"<g
-     inkscape:label="Layer 1"
-     inkscape:groupmode="layer"
-     id="layer1"
-     transform="translate(0,-2e-4)">
-    <polygon
-       fill="#929497"
-       points="144,0 0,0 0,198 144,198 "
-       id="polygon1000"
-       transform="matrix(1.1024306,0,0,1.1024306,0,2e-4)"
-       style="fill:#f1f2f2;fill-opacity:1;stroke-width:0.24" />
-    <path
-       fill="#ffffff"
-       stroke="#ec1e28"
-       stroke-width="0.374174"
-       d="M 138.9062,208.3596 H 19.8437 c -5.4799,0 -9.9221,-4.4417 -9.9221,-9.9219 V 19.844 c 0,-5.4802 4.4422,-9.9219 9.9221,-9.9219 h 119.0625 c 5.48,0 9.9218,4.4417 9.9218,9.9219 v 178.5937 c 0,5.4802 -4.4418,9.9219 -9.9218,9.9219 z"
-       id="path1000"
-       style="fill:#f1f2f2;fill-opacity:1;stroke:none" />
-    <path
-       id="path1023"
-       style="fill:#d6d6d6;fill-opacity:1;stroke:none;stroke-width:12.3172;stroke-linecap:round;stroke-linejoin:round;stroke-miterlimit:4;stroke-dasharray:none;stroke-dashoffset:0;stroke-opacity:1"
-       d="M 0,69.4531 V 0 H 71.4633 L 79.375,8.355 87.2867,0 H 158.75 v 69.4531 z" />
-    <path
-       id="path1001"
-       style="fill:#3f3f3f;fill-opacity:1;stroke:none;stroke-width:12.3172;stroke-linecap:round;stroke-linejoin:round;stroke-miterlimit:4;stroke-dasharray:none;stroke-dashoffset:0;stroke-opacity:1"
-       d="m 0,0 v 69.4531 l 71.351056,0 8.023943,-8.473528 8.023943,8.473528 71.351058,0 V 0 Z"
-       sodipodi:nodetypes="cccccccc" />
-    <text
-       xml:space="preserve"
-       style="font-style:normal;font-variant:normal;font-weight:500;font-stretch:normal;font-size:49.3895px;line-height:1.25;font-family:Rubik;-inkscape-font-specification:'Rubik Medium';text-align:center;letter-spacing:0px;word-spacing:0px;text-anchor:middle;fill:#eeeeee;fill-opacity:1;stroke:none;stroke-width:1.23474"
-       x="{number_offset}"
-       y="57.742939"
-       id="text1001"><tspan
-         sodipodi:role="line"
-         id="tspan1001"
-         x="{number_offset}"
-         y="57.742939"
-         style="font-style:normal;font-variant:normal;font-weight:500;font-stretch:normal;font-family:Rubik;-inkscape-font-specification:'Rubik Medium';text-align:center;text-anchor:middle;fill:#eeeeee;fill-opacity:1;stroke-width:1.23474">{scores}</tspan></text>
-    {inserting}
-  </g>"##,
-    ).unwrap();
+    let background = printed_background(CARD_BACKGROUND_COLOR);
+    let cutline = cutline_style(background);
+    // low ink printing lightens the bar and darkens the score to stay readable on it
+    let (bar_shadow, bar, score_color) = if card_print_conf().low_ink {
+        ("ebebeb", "cfcfcf", "3f3f3f")
+    } else {
+        ("d6d6d6", "3f3f3f", "eeeeee")
+    };
+    let bar_style = |color: &str| format!("fill:#{color};fill-opacity:1;stroke:none;stroke-width:12.3172;stroke-linecap:round;stroke-linejoin:round;stroke-miterlimit:4;stroke-dasharray:none;stroke-dashoffset:0;stroke-opacity:1");
+    let score_at = V2::new(number_offset, 57.742939);
+    layer()
+        .transform(&[Transform::Translate(V2::new(0.0, -2e-4))])
+        .child(
+            polygon(&card_background_points())
+                .attr("fill", "#929497")
+                .id("polygon1000")
+                .transform(&[CARD_BACKGROUND_TRANSFORM])
+                .style(format!("fill:#{background};fill-opacity:1;stroke-width:0.24")),
+        )
+        .child(
+            path(&cutline_path())
+                .attr("fill", "#ffffff")
+                .attr("stroke", "#ec1e28")
+                .attr("stroke-width", "0.374174")
+                .id("path1000")
+                .style(cutline),
+        )
+        .child(
+            path("M 0,69.4531 V 0 H 71.4633 L 79.375,8.355 87.2867,0 H 158.75 v 69.4531 z")
+                .id("path1023")
+                .style(bar_style(bar_shadow)),
+        )
+        .child(
+            path("m 0,0 v 69.4531 l 71.351056,0 8.023943,-8.473528 8.023943,8.473528 71.351058,0 V 0 Z")
+                .id("path1001")
+                .style(bar_style(bar))
+                .attr("sodipodi:nodetypes", "cccccccc"),
+        )
+        .child(
+            text(score_at)
+                .style(format!("font-style:normal;font-variant:normal;font-weight:500;font-stretch:normal;font-size:49.3895px;line-height:1.25;font-family:Rubik;-inkscape-font-specification:'Rubik Medium';text-align:center;letter-spacing:0px;word-spacing:0px;text-anchor:middle;fill:#{score_color};fill-opacity:1;stroke:none;stroke-width:1.23474"))
+                .id("text1001")
+                .child(
+                    tspan(score_at, &scores)
+                        .attr("sodipodi:role", "line")
+                        .id("tspan1001")
+                        .style(format!("font-style:normal;font-variant:normal;font-weight:500;font-stretch:normal;font-family:Rubik;-inkscape-font-specification:'Rubik Medium';text-align:center;text-anchor:middle;fill:#{score_color};fill-opacity:1;stroke-width:1.23474")),
+                ),
+        )
+        .child(raw_display(inserting))
+        .write_to(to);
 }
 
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum CardSpecKind {
     Kill,
     Move,
@@ -646,21 +723,64 @@ pub enum CardSpecKind {
 }
 pub use CardSpecKind::*;
 
-use crate::clear_or_create;
+use crate::{
+    circle, clear_or_create, el, element_mark, g, layer, layout_text, path, polygon, raw_display, rect, Element, Node, report_text_overflow, take_text_overflows, text, tspan, with_element_mark, Desire, Transform, DESCRIPTION_FONT,
+    DESCRIPTION_FONT_SIZE, DESCRIPTION_LINE_HEIGHT, DESCRIPTION_MIN_FONT_SIZE, description_rect,
+};
+// which pile a card goes in. Also the order they're printed in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Deck {
+    Ends,
+    Means,
+    // the cards that decide whether the game ends
+    Events,
+    Land,
+}
+impl Deck {
+    pub fn name(self) -> &'static str {
+        match self {
+            Deck::Ends => "ends",
+            Deck::Means => "means",
+            Deck::Events => "events",
+            Deck::Land => "land",
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct CardSpec {
     // likes: Vec<ElementTag>,
     pub name: String,
     pub repeat: usize,
     pub level: usize,
+    pub clown: bool,
+    pub deck: Deck,
     pub properties: Vec<(CardSpecKind, Vec<ElementTag>)>,
     // the amount this type of card's frequency should be changed from its baseline frequency
     pub frequency_modifier: f64,
     // the ratio of cards that are from this generator
     pub generate_front: Rc<dyn Fn(&mut dyn Write)>,
     pub generate_back: Rc<dyn Fn(&mut dyn Write)>,
+    // for end cards, how they score over the lands, where that's something we can compute
+    pub desire: Option<Desire>,
+    // the text on the back, with its {icon} markup
+    pub description: String,
 }
 impl CardSpec {
+    // the set of print and play sheets this card goes on, eg "means_level1" or "ends_level2_clown". Lands don't have levels.
+    pub fn pnp_group(&self) -> String {
+        if self.deck == Deck::Land {
+            return self.deck.name().to_string();
+        }
+        let clown = if self.clown { "_clown" } else { "" };
+        format!("{}_level{}{clown}", self.deck.name(), self.level)
+    }
+    pub fn with_desire(self, desire: Desire) -> Self {
+        Self {
+            desire: Some(desire),
+            ..self
+        }
+    }
     pub fn has_property(&self, p: CardSpecKind, e: ElementTag) -> bool {
         self.properties
             .iter()
@@ -707,10 +827,13 @@ impl CardSpec {
         } else {
             name.clone()
         };
+        let description = back_text.clone();
         Self {
             name: filename,
             repeat: repeated,
             frequency_modifier: 1.0,
+            clown,
+            deck: Deck::Means,
             level,
             generate_front: {
                 let front_graphic = front_graphic.clone();
@@ -740,6 +863,8 @@ impl CardSpec {
                 }
             }),
             properties,
+            desire: None,
+            description,
         }
     }
     pub fn end_card_with_back_blurred_message(
@@ -755,9 +880,12 @@ impl CardSpec {
     ) -> Self {
         let rcd = Rc::new(front_graphic);
         let sc = score.clone();
+        let description = back_text.clone();
         Self {
             name,
             repeat,
+            clown,
+            deck: Deck::Ends,
             level,
             generate_front: {
                 let front_inner = rcd.clone();
@@ -785,8 +913,83 @@ impl CardSpec {
             }),
             frequency_modifier: 1.0,
             properties: vec![(Preference, elements_positive)],
+            desire: None,
+            description,
+        }
+    }
+}
+
+// cards drawn in inkscape rather than generated. Files are named name[face,N].svg and name[back].svg, where N is how many copies to print (1 if it's left out). The face's brackets can also say levelN (default 1) and clown, eg, name[face,2,level2,clown].svg
+pub fn hand_made_cards(dir: &Path, deck: Deck) -> Vec<CardSpec> {
+    struct Sides {
+        repeat: usize,
+        level: usize,
+        clown: bool,
+        front: Option<Rc<Vec<u8>>>,
+        back: Option<Rc<Vec<u8>>>,
+    }
+    let mut by_name: HashMap<String, Sides> = HashMap::new();
+    let Ok(dens) = std::fs::read_dir(dir) else {
+        return vec![];
+    };
+    for item in dens.flatten() {
+        let file_name = item.file_name().to_string_lossy().to_string();
+        let Some((name, rest)) = file_name.split_once('[') else {
+            continue;
+        };
+        let Some((within_brackets, _)) = rest.split_once(']') else {
+            continue;
+        };
+        let mut fields = within_brackets.split(',');
+        let is_front = fields.next() == Some("face");
+        let data = Rc::new(std::fs::read(item.path()).unwrap());
+        let sides = by_name.entry(name.to_string()).or_insert(Sides {
+            repeat: 1,
+            level: 1,
+            clown: false,
+            front: None,
+            back: None,
+        });
+        if is_front {
+            for f in fields {
+                if f == "clown" {
+                    sides.clown = true;
+                } else if let Some(l) = f.strip_prefix("level") {
+                    sides.level = l
+                        .parse()
+                        .unwrap_or_else(|_| panic!("bad level in {file_name}"));
+                } else {
+                    sides.repeat = f
+                        .parse()
+                        .unwrap_or_else(|_| panic!("bad repeat count in {file_name}"));
+                }
+            }
+            sides.front = Some(data);
+        } else {
+            sides.back = Some(data);
         }
     }
+    let mut r = Vec::new();
+    for (name, sides) in by_name.into_iter() {
+        let (Some(front), Some(back)) = (sides.front, sides.back) else {
+            println!("warning, {name} in {} is missing a side", dir.display());
+            continue;
+        };
+        r.push(CardSpec {
+            name,
+            repeat: sides.repeat,
+            level: sides.level,
+            clown: sides.clown,
+            deck,
+            properties: vec![],
+            frequency_modifier: 1.0,
+            generate_front: Rc::new(move |w| w.write_all(&front).unwrap()),
+            generate_back: Rc::new(move |w| w.write_all(&back).unwrap()),
+            desire: None,
+            description: String::new(),
+        });
+    }
+    r
 }
 
 pub fn end_backing(
@@ -808,7 +1011,7 @@ pub fn backing(
     clown: bool,
     is_end: bool,
 ) {
-    let span = CARD_DIMENSIONS.x;
+    let span = card_dimensions().x;
     let sep = span * 0.05;
     let level_marker = Displaying(|w| {
         let origin = cutline_bounds().br - V2::new(0.0, span*0.13);
@@ -848,68 +1051,85 @@ pub fn backing(
             }
         }
     });
-    write!(to,
-r##"
-
-<defs
-     id="defs1">
-    <rect
-       x="73.083376"
-       y="74.501079"
-       width="454.10823"
-       height="671.09289"
-       id="descriptionrect" />
-    <filter
-       inkscape:collect="always"
-       style="color-interpolation-filters:sRGB"
-       id="flipfilter"
-       x="-0.056058263"
-       y="-0.040769646"
-       width="1.1121165"
-       height="1.0815393">
-      <feGaussianBlur
-         inkscape:collect="always"
-         stdDeviation="3.4"
-         id="feGaussianBlur5" />
-    </filter>
-  </defs>
-  <g
-     inkscape:label="Layer 1"
-     inkscape:groupmode="layer"
-     id="layer1"
-     transform="translate(0,0)">
-    <polygon
-       fill="#929497"
-       points="144,198 144,0 0,0 0,198"
-       id="assetback"
-       transform="matrix(1.1024306,0,0,1.1024306,0,2e-4)"
-       style="fill:#f1f2f2;fill-opacity:1;stroke-width:0.24" />
-    <g transform="matrix(-1,0,0,1,{span},0)" style="opacity:0.55;filter:url(#flipfilter)">
-    <g style="opacity:0.5">
-        {end_bar}
-    </g>
-    {inserting}
-    </g>
-    {level_marker}
-    <text
-       xml:space="preserve"
-       transform="matrix(0.26458333,0,0,0.26458333,-0.21640517,0)"
-       id="text1"
-       style="font-weight:900;font-size:43px;font-family:'Inter UI';-inkscape-font-specification:'Rubik';text-align:center;vertical-align:bottom;white-space:pre;shape-inside:url(#descriptionrect);opacity:1;fill:#3e3e3e;fill-opacity:1;stroke:none;stroke-width:7.55906;stroke-linecap:round;stroke-linejoin:round"><tspan
-         x="93.067162"
-         y="126.73272"
-         id="tspan3"><tspan
-           style="font-weight:normal;font-family:Rubik;-inkscape-font-specification:Rubik"
-           id="tspan2">{description}</tspan></tspan></text>
-  </g>
-"##,
-    ).unwrap();
+    let description_layout = layout_text(
+        description,
+        description_rect(),
+        DESCRIPTION_FONT_SIZE,
+        DESCRIPTION_MIN_FONT_SIZE,
+        DESCRIPTION_LINE_HEIGHT,
+        &|name| assets.icon(name).is_some(),
+    );
+    report_text_overflow(&description_layout, description);
+    let font_size = description_layout.font_size;
+    let description_lines =
+        Displaying(|w| description_layout.write_tspans(description_rect(), w));
+    let description_icons = Displaying(|w| {
+        for (name, bounds) in description_layout.icons(description_rect()) {
+            assets.icon(name).unwrap().center_in_bounds(bounds, w);
+        }
+    });
+    let background = printed_background(CARD_BACKGROUND_COLOR);
+    // the front showing through from behind, blurred. Low ink printing leaves it out.
+    let flipped_front = Displaying(|w| {
+        if !card_print_conf().low_ink {
+            g().transform(&[Transform::Matrix([-1.0, 0.0, 0.0, 1.0, span, 0.0])])
+                .style("opacity:0.55;filter:url(#flipfilter)")
+                .child(g().style("opacity:0.5").child(raw_display(&end_bar)))
+                .child(raw_display(inserting))
+                .write_to(w);
+        }
+    });
+    let defs = el("defs").id("defs1").child(
+        el("filter")
+            .attr("inkscape:collect", "always")
+            .style("color-interpolation-filters:sRGB")
+            .id("flipfilter")
+            .attr("x", "-0.056058263")
+            .attr("y", "-0.040769646")
+            .attr("width", "1.1121165")
+            .attr("height", "1.0815393")
+            .child(
+                el("feGaussianBlur")
+                    .attr("inkscape:collect", "always")
+                    .attr("stdDeviation", "3.4")
+                    .id("feGaussianBlur5"),
+            ),
+    );
+    // the description is in a space scaled down by 0.26458333, the px per mm of inkscape's templates
+    let description_space = Transform::Matrix([0.26458333, 0.0, 0.0, 0.26458333, -0.21640517, 0.0]);
+    let content = layer()
+        .transform(&[Transform::Translate(V2::new(0.0, 0.0))])
+        .child(
+            polygon(&card_background_points())
+                .attr("fill", "#929497")
+                .id("assetback")
+                .transform(&[CARD_BACKGROUND_TRANSFORM])
+                .style(format!("fill:#{background};fill-opacity:1;stroke-width:0.24")),
+        )
+        .child(raw_display(&flipped_front))
+        .child(raw_display(&level_marker))
+        .child(
+            el("text")
+                .attr("xml:space", "preserve")
+                .transform(&[description_space])
+                .id("text1")
+                .style(format!("font-weight:normal;font-size:{font_size}px;font-family:{DESCRIPTION_FONT};-inkscape-font-specification:{DESCRIPTION_FONT};text-align:center;text-anchor:middle;opacity:1;fill:#3e3e3e;fill-opacity:1;stroke:none;stroke-width:7.55906;stroke-linecap:round;stroke-linejoin:round"))
+                .child(raw_display(&description_lines)),
+        )
+        .child(
+            g().transform(&[description_space])
+                .child(raw_display(&description_icons)),
+        );
+    write_namespaced("backing", to, |to| {
+        defs.write_to(to);
+        content.write_to(to);
+    });
 }
 
 pub fn just_1(color: &str, to: &mut dyn Write) {
     let scale = 1.5;
     let offset = offset_for_grav(
-        END_GRAPHIC_CENTER - V2::new(0.0, 0.23 * GRAPHIC_RAD),
+        end_graphic_center() - V2::new(0.0, 0.23 * graphic_rad()),
         MIDDLE_BOTTOM,
         V2::new(27.831, 27.318) * scale,
     );
@@ -917,7 +1137,6 @@ pub fn just_1(color: &str, to: &mut dyn Write) {
         r##"<g transform="translate({},{}) scale({scale})"><g
      inkscape:label="Layer 1"
      inkscape:groupmode="layer"
-     id="layer1"
      transform="translate(-38.099981,-226.48331)">
     <text
        xml:space="preserve"
@@ -942,8 +1161,7 @@ pub fn underline(color: &str, anchor: V2, grav: V2, hspan: f64, to: &mut dyn Wri
     write!(to, r##"<g
      inkscape:label="Layer 1"
      inkscape:groupmode="layer"
-     transform="translate({},{}) scale({scale})"
-     id="layer1">
+     transform="translate({},{}) scale({scale})">
     <rect
        transform="scale(-1)"
        ry="1.9218473"
@@ -973,7 +1191,7 @@ pub fn big_splat(color: &str, to: &mut dyn Write) {
 
 pub fn big_splat_scaled(color: &str, scale: f64, to: &mut dyn Write) {
     let offset = offset_for_grav_scale(
-        END_GRAPHIC_CENTER,
+        end_graphic_center(),
         MIDDLE_MIDDLE,
         V2::new(205.184, 224.671),
         scale,
@@ -982,8 +1200,7 @@ pub fn big_splat_scaled(color: &str, scale: f64, to: &mut dyn Write) {
         r##"<g
      inkscape:label="Layer 1"
      inkscape:groupmode="layer"
-     transform="translate({},{}) scale({scale})"
-     id="layer1">
+     transform="translate({},{}) scale({scale})">
     <path
        id="path22"
        style="color:#{color};fill:#{color};stroke-linecap:round;stroke-linejoin:round;-inkscape-stroke:none"
@@ -1022,8 +1239,7 @@ pub fn road_blob_rad(
         r##"<g
      inkscape:label="Layer 1"
      inkscape:groupmode="layer"
-     transform="translate({},{}) scale({scale})"
-     id="layer1">
+     transform="translate({},{}) scale({scale})">
     <path
        id="rect2665"
        style="fill:#{color};fill-opacity:1;stroke-width:1.48762;stroke-linecap:round;stroke-linejoin:round;stroke-opacity:0.20634"
@@ -1040,12 +1256,11 @@ pub fn road_blob_rad(
 
 pub fn negatory(to: &mut dyn Write) {
     // let scale = 0.54;
-    let offset = offset_for_grav(END_GRAPHIC_CENTER, MIDDLE_MIDDLE, V2::new(122.431, 78.813));
+    let offset = offset_for_grav(end_graphic_center(), MIDDLE_MIDDLE, V2::new(122.431, 78.813));
     write!(to,
         r##"<g
      inkscape:label="Layer 1"
      inkscape:groupmode="layer"
-     id="layer1"
      transform="translate({},{})">
     <rect
        transform="matrix(0.86602609,-0.49999881,0.5000012,0.86602471,0,0)"
@@ -1075,7 +1290,7 @@ pub fn tilted_pair(center: V2, distance: f64) -> (V2, V2) {
 pub fn paired(e1: ElementTag, e2: ElementTag, flipped: bool, to: &mut dyn Write) {
     let sized = 0.55;
     let spaced = 0.08;
-    let (mut c1, mut c2) = tilted_pair(END_GRAPHIC_CENTER, (sized + spaced) * BIG_ELEMENT_RAD);
+    let (mut c1, mut c2) = tilted_pair(end_graphic_center(), (sized + spaced) * BIG_ELEMENT_RAD);
     if flipped {
         std::mem::swap(&mut c1.y, &mut c2.y);
     }
@@ -1136,7 +1351,7 @@ pub fn cross<A: Clone, B>(
     })
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub struct Coord {
     pub x: i32,
     pub y: i32,
@@ -1148,12 +1363,26 @@ impl Coord {
     pub const AWARD: Coord = Coord { x: -1, y: 0 };
     pub const ZWARD: Coord = Coord { x: 0, y: -1 };
     pub const XWARD: Coord = Coord { x: 1, y: -1 };
+    pub const DIRECTIONS: [Coord; 6] = [
+        Coord::DWARD,
+        Coord::EWARD,
+        Coord::WWARD,
+        Coord::AWARD,
+        Coord::ZWARD,
+        Coord::XWARD,
+    ];
     pub fn new(x: i32, y: i32) -> Self {
         Coord { x, y }
     }
     pub fn to_v2(self) -> V2 {
         V2::new(self.x as f64, self.y as f64)
     }
+    pub fn offset(self, by: Coord) -> Coord {
+        Coord::new(self.x + by.x, self.y + by.y)
+    }
+    pub fn neighbors(self) -> impl Iterator<Item = Coord> {
+        Coord::DIRECTIONS.into_iter().map(move |d| self.offset(d))
+    }
 }
 
 pub struct HexSpiral {
@@ -1259,7 +1488,7 @@ pub fn unhexify(v: V2) -> V2 {
 //end generalizable util stuff
 
 pub fn end_graphic_usual_bounds() -> Rect {
-    Rect::from_center_radii(END_GRAPHIC_CENTER, V2::from_element(GRAPHIC_RAD))
+    Rect::from_center_radii(end_graphic_center(), V2::from_element(graphic_rad()))
 }
 pub fn end_graphic_usual_bounds_shrunk_appropriately() -> Rect {
     end_graphic_usual_bounds().shrunk(0.8)
@@ -1269,30 +1498,48 @@ pub fn cutline_bounds_shrunk_appropriately() -> Rect {
 }
 pub fn cutline_bounds() -> Rect {
     Rect {
-        ul: CUTLINE_INSET,
-        br: CARD_DIMENSIONS - CUTLINE_INSET,
+        ul: cutline_inset(),
+        br: card_dimensions() - cutline_inset(),
     }
 }
+// the cut, less the strip along the bottom where the name goes
 pub fn means_graphic_usual_bounds() -> Rect {
-    let ul = V2::new(9.922, 9.922);
+    let cut = cutline_bounds();
     Rect {
-        ul,
-        br: ul + V2::new(138.906, 162.991),
+        ul: cut.ul,
+        br: cut.br - V2::new(0.0, 35.4465),
     }
 }
 pub fn means_graphic_usual_bounds_shrunk_appropriately() -> Rect {
     means_graphic_usual_bounds().shrunk(0.8)
 }
 pub fn card_upper_center() -> V2 {
-    V2::new(CARD_DIMENSIONS.x / 2.0, CARD_DIMENSIONS.x / 2.0)
+    let dims = card_dimensions();
+    V2::new(dims.x / 2.0, dims.x / 2.0)
 }
 pub fn card_lower_center() -> V2 {
-    V2::new(
-        CARD_DIMENSIONS.x / 2.0,
-        CARD_DIMENSIONS.y - CARD_DIMENSIONS.x / 2.0,
+    let dims = card_dimensions();
+    V2::new(dims.x / 2.0, dims.y - dims.x / 2.0)
+}
+// a rounded rectangle, drawn the way inkscape draws them, for the cutline and the print and play mask
+pub fn rounded_rect_path(r: &Rect, radius: f64) -> String {
+    // the control points of a quarter circle
+    let k = radius * 0.55228;
+    let w = r.width() - 2.0 * radius;
+    let h = r.height() - 2.0 * radius;
+    format!(
+        "M {},{} H {} c {},0 {},{} {},{} V {} c 0,{} {},{} {},{} h {} c {},0 {},{} {},{} v {} c 0,{} {},{} {},{} z",
+        r.br.x - radius, r.br.y,
+        r.ul.x + radius,
+        -k, -radius, -(radius - k), -radius, -radius,
+        r.ul.y + radius,
+        -k, radius - k, -radius, radius, -radius,
+        w,
+        k, radius, radius - k, radius, radius,
+        h,
+        k, -(radius - k), radius, -radius, radius,
     )
 }
-
 // //wait, this would break if you had nested svg elements. Fuck streaming parsers.
 // fn parse_extract(at:&std::path::Path)-> Result<(String, V2), Box<dyn Error>> {
 //     use quick_xml::events::{Event, BytesEnd, BytesStart};
@@ -1422,24 +1669,394 @@ impl Asset {
     }
 }
 
-pub fn load_asset(at: &Path, anchor: Option<V2>) -> Asset {
-    // pub fn for_asset(at: &std::path::Path) -> Rc<dyn Display> {
-    let assetxml = elementtree::Element::from_reader(&std::fs::File::open(at).unwrap()).unwrap_or_else(|e| panic!("couldn't parse {:?}. {:?}", at, e));
+// the id and contents of each symbol a document has collected
+type SymbolScope = Vec<(String, Rc<Vec<u8>>)>;
+// each document (see svg_outer, card_outer, end_outer) collects the assets placed in it, and writes each of them only once, as a <symbol> in its <defs>. Every placement is then just a <use>. Documents can be generated while another is being written, so it's a stack.
+thread_local! {
+    static SYMBOL_SCOPES: RefCell<Vec<SymbolScope>> = const { RefCell::new(Vec::new()) };
+    static NEXT_UNIQUE_ID: Cell<usize> = const { Cell::new(0) };
+}
+// unique for the whole run, so that documents nested in other documents can't confuse their ids either
+pub fn unique_id(name: &str) -> String {
+    let n = NEXT_UNIQUE_ID.with(|c| c.replace(c.get() + 1));
+    let readable: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("{readable}{n}")
+}
+
+// fragments pasted from inkscape have ids like layer1 and clipPath3, and a document that has two of them can end up clipping one with the other's clip path. This gives every id the fragment defines the prefix, and updates the fragment's references to them, url(#..) (quoted or not) and href="#..". References to ids defined outside the fragment are left alone.
+pub fn namespace_ids(fragment: &str, prefix: &str) -> String {
+    let spans = id_spans(fragment);
+    let defined: HashSet<&str> = spans
+        .iter()
+        .filter(|(definition, _)| *definition)
+        .map(|(_, s)| &fragment[s.clone()])
+        .collect();
+    let mut r = String::with_capacity(fragment.len() + prefix.len() * spans.len());
+    let mut written = 0;
+    for (_, s) in spans {
+        if defined.contains(&fragment[s.clone()]) {
+            r.push_str(&fragment[written..s.start]);
+            r.push_str(prefix);
+            written = s.start;
+        }
+    }
+    r.push_str(&fragment[written..]);
+    r
+}
+// every id attribute (true) and every reference to an id (false) in the fragment, as the range of the id itself, in one pass. Only attributes named exactly id count, not data-id and the like.
+fn id_spans(fragment: &str) -> Vec<(bool, Range<usize>)> {
+    let b = fragment.as_bytes();
+    let mut r = Vec::new();
+    let mut i = 0;
+    while i < b.len() {
+        let span = match b[i] {
+            b'=' => {
+                let name_start = fragment[..i]
+                    .rfind(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, ':' | '-' | '_')))
+                    .map_or(0, |p| p + 1);
+                let name = &fragment[name_start..i];
+                let attribute = name_start > 0 && b[name_start - 1].is_ascii_whitespace();
+                let quote = b.get(i + 1).copied().filter(|q| matches!(q, b'"' | b'\''));
+                match (attribute, quote) {
+                    (true, Some(q)) if name == "id" => quoted_from(fragment, i + 2, q).map(|s| (true, s)),
+                    (true, Some(q)) if (name == "href" || name.ends_with(":href")) && b.get(i + 2) == Some(&b'#') => {
+                        quoted_from(fragment, i + 3, q).map(|s| (false, s))
+                    }
+                    _ => None,
+                }
+            }
+            b'(' if fragment[..i].ends_with("url") => {
+                let quote = b.get(i + 1).copied().filter(|q| matches!(q, b'"' | b'\''));
+                let hash = i + 1 + quote.is_some() as usize;
+                if b.get(hash) == Some(&b'#') {
+                    quoted_from(fragment, hash + 1, quote.unwrap_or(b')')).map(|s| (false, s))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+        match span {
+            Some(span) => {
+                i = span.1.end;
+                r.push(span);
+            }
+            None => i += 1,
+        }
+    }
+    r
+}
+// from start up to the closing character
+fn quoted_from(fragment: &str, start: usize, close: u8) -> Option<Range<usize>> {
+    let len = fragment.get(start..)?.find(close as char)?;
+    Some(start..start + len)
+}
+// for composing fragments with ids in them. Writes whatever fragment writes with its ids namespaced under a fresh prefix.
+pub fn write_namespaced(name: &str, to: &mut dyn Write, fragment: impl FnOnce(&mut dyn Write)) {
+    let mut buffer = Vec::new();
+    fragment(&mut buffer);
+    let prefix = format!("{}-", unique_id(name));
+    to.write_all(namespace_ids(&String::from_utf8(buffer).unwrap(), &prefix).as_bytes())
+        .unwrap();
+}
+pub fn begin_symbols() {
+    SYMBOL_SCOPES.with(|s| s.borrow_mut().push(Vec::new()));
+}
+// ends the document begun most recently, formatting to its <defs>. It goes after the content, so that it knows what was used, references in svg can point forwards.
+pub struct SymbolDefs;
+impl Display for SymbolDefs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let symbols = SYMBOL_SCOPES.with(|s| s.borrow_mut().pop()).unwrap_or_default();
+        if symbols.is_empty() {
+            return Ok(());
+        }
+        writeln!(f, "<defs>")?;
+        for (id, content) in symbols {
+            writeln!(
+                f,
+                r#"<symbol id="{id}" overflow="visible">{}</symbol>"#,
+                String::from_utf8_lossy(&content)
+            )?;
+        }
+        writeln!(f, "</defs>")
+    }
+}
+// renders as if no document were being written, so that assets write their content inline instead of a <use>
+fn without_symbols<R>(f: impl FnOnce() -> R) -> R {
+    let scopes = SYMBOL_SCOPES.with(|s| s.take());
+    let r = f();
+    SYMBOL_SCOPES.with(|s| *s.borrow_mut() = scopes);
+    r
+}
+// writes a <use> of the content, registering it with the current document, or returns false if no document is being written, in which case the caller should just write the content
+fn use_symbol(id: &str, content: &Rc<Vec<u8>>, to: &mut dyn Write) -> bool {
+    let in_document = SYMBOL_SCOPES.with(|s| {
+        let mut s = s.borrow_mut();
+        let Some(scope) = s.last_mut() else {
+            return false;
+        };
+        if !scope.iter().any(|(i, _)| i == id) {
+            scope.push((id.to_string(), content.clone()));
+        }
+        true
+    });
+    if in_document {
+        write!(
+            to,
+            r##"<use xmlns:xlink="http://www.w3.org/1999/xlink" xlink:href="#{id}" />"##
+        )
+        .unwrap();
+    }
+    in_document
+}
+
+pub fn load_asset(at: &Path, anchor: Option<V2>) -> Result<Asset, String> {
+    let name = at.file_stem().unwrap_or_default().to_string_lossy();
+    let file = std::fs::File::open(at).map_err(|e| format!("{}: couldn't open it, {e}", at.display()))?;
+    let assetxml = elementtree::Element::from_reader(&file)
+        .map_err(|e| format!("{}: isn't valid xml, {e}", at.display()))?;
+    asset_from_svg(&assetxml, &name, anchor).map_err(|e| format!("{}: {e}", at.display()))
+}
+// for svgs we've generated in memory, eg, a card's front
+pub fn asset_from_svg_data(data: &[u8], name: &str, anchor: Option<V2>) -> Asset {
+    let assetxml = elementtree::Element::from_reader(data)
+        .unwrap_or_else(|e| panic!("couldn't parse {name}. {:?}", e));
+    asset_from_svg(&assetxml, name, anchor).unwrap_or_else(|e| panic!("{name}: {e}"))
+}
+
+// an svg length in design units (mm). Unitless lengths are user units, which are css px.
+fn parse_length(v: &str) -> Result<f64, String> {
+    let v = v.trim();
+    let units = [
+        ("mm", 1.0),
+        ("cm", 10.0),
+        ("in", 25.4),
+        ("pt", 25.4 / 72.0),
+        ("pc", 25.4 / 6.0),
+        ("px", 25.4 / 96.0),
+        ("Q", 0.25),
+    ];
+    if v.ends_with('%') || v.ends_with("em") || v.ends_with("ex") {
+        return Err(format!(
+            "the length {v:?} is relative to something an asset doesn't have, give it an absolute unit (mm, px, in...)"
+        ));
+    }
+    let (number, per_unit) = units
+        .iter()
+        .find_map(|&(u, s)| v.strip_suffix(u).map(|n| (n, s)))
+        .unwrap_or((v, 25.4 / 96.0));
+    let n: f64 = number
+        .trim()
+        .parse()
+        .map_err(|_| format!("couldn't read the length {v:?}"))?;
+    if !(n > 0.0 && n.is_finite()) {
+        return Err(format!("the length {v:?} isn't positive"));
+    }
+    Ok(n * per_unit)
+}
+
+fn parse_view_box(v: &str) -> Result<Rect, String> {
+    let ns: Vec<f64> = v
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse::<f64>())
+        .collect::<Result<_, _>>()
+        .map_err(|_| format!("couldn't read the viewBox {v:?}"))?;
+    if ns.len() != 4 {
+        return Err(format!("the viewBox {v:?} should be four numbers"));
+    }
+    if !(ns[2] > 0.0 && ns[3] > 0.0) {
+        return Err(format!("the viewBox {v:?} has no area"));
+    }
+    Ok(Rect {
+        ul: V2::new(ns[0], ns[1]),
+        br: V2::new(ns[0] + ns[2], ns[1] + ns[3]),
+    })
+}
+
+// the matrix taking viewBox coordinates to design units, following preserveAspectRatio (default xMidYMid meet)
+fn view_box_transform(view_box: &Rect, size: V2, preserve: Option<&str>) -> Result<[f64; 6], String> {
+    let preserve = preserve.unwrap_or("xMidYMid meet");
+    let mut words = preserve.split_whitespace();
+    let align = words.next().unwrap_or("xMidYMid");
+    let fit = words.next().unwrap_or("meet");
+    let sx = size.x / view_box.width();
+    let sy = size.y / view_box.height();
+    if align == "none" {
+        return Ok([sx, 0.0, 0.0, sy, -view_box.ul.x * sx, -view_box.ul.y * sy]);
+    }
+    let s = match fit {
+        "meet" => sx.min(sy),
+        "slice" => sx.max(sy),
+        _ => return Err(format!("couldn't read preserveAspectRatio {preserve:?}")),
+    };
+    let fraction = |a: Option<&str>| match a {
+        Some("Min") => Ok(0.0),
+        Some("Mid") => Ok(0.5),
+        Some("Max") => Ok(1.0),
+        _ => Err(format!("couldn't read preserveAspectRatio {preserve:?}")),
+    };
+    let ax = fraction(align.get(1..4))?;
+    let ay = fraction(align.get(5..8))?;
+    Ok([
+        s,
+        0.0,
+        0.0,
+        s,
+        -view_box.ul.x * s + ax * (size.x - view_box.width() * s),
+        -view_box.ul.y * s + ay * (size.y - view_box.height() * s),
+    ])
+}
+
+// an svg transform attribute as a matrix, a b c d e f
+fn parse_transform(v: &str) -> Result<[f64; 6], String> {
+    let mut m = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+    for part in v.split(')').map(|p| p.trim_start_matches(|c: char| c.is_whitespace() || c == ',')).filter(|p| !p.trim().is_empty()) {
+        let bad = || format!("couldn't read the transform {v:?}");
+        let (kind, args) = part.split_once('(').ok_or_else(bad)?;
+        let args: Vec<f64> = args
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|a| !a.is_empty())
+            .map(|a| a.parse::<f64>())
+            .collect::<Result<_, _>>()
+            .map_err(|_| bad())?;
+        let t = match (kind.trim(), args.as_slice()) {
+            ("matrix", &[a, b, c, d, e, f]) => [a, b, c, d, e, f],
+            ("translate", &[x]) => [1.0, 0.0, 0.0, 1.0, x, 0.0],
+            ("translate", &[x, y]) => [1.0, 0.0, 0.0, 1.0, x, y],
+            ("scale", &[s]) => [s, 0.0, 0.0, s, 0.0, 0.0],
+            ("scale", &[x, y]) => [x, 0.0, 0.0, y, 0.0, 0.0],
+            ("rotate", &[r]) => rotation_about(r, V2::zeros()),
+            ("rotate", &[r, x, y]) => rotation_about(r, V2::new(x, y)),
+            ("skewX", &[a]) => [1.0, 0.0, a.to_radians().tan(), 1.0, 0.0, 0.0],
+            ("skewY", &[a]) => [1.0, a.to_radians().tan(), 0.0, 1.0, 0.0, 0.0],
+            _ => return Err(bad()),
+        };
+        m = compose(m, t);
+    }
+    Ok(m)
+}
+fn rotation_about(degrees: f64, c: V2) -> [f64; 6] {
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    [cos, sin, -sin, cos, c.x - cos * c.x + sin * c.y, c.y - sin * c.x - cos * c.y]
+}
+// applies inner first
+fn compose(outer: [f64; 6], inner: [f64; 6]) -> [f64; 6] {
+    let [a, b, c, d, e, f] = outer;
+    let [ia, ib, ic, id, ie, iff] = inner;
+    [
+        a * ia + c * ib,
+        b * ia + d * ib,
+        a * ic + c * id,
+        b * ic + d * id,
+        a * ie + c * iff + e,
+        b * ie + d * iff + f,
+    ]
+}
+fn apply(m: [f64; 6], p: V2) -> V2 {
+    V2::new(m[0] * p.x + m[2] * p.y + m[4], m[1] * p.x + m[3] * p.y + m[5])
+}
+
+const INKSCAPE_NS: &str = "http://www.inkscape.org/namespaces/inkscape";
+fn is_anchor_marker(e: &elementtree::Element) -> bool {
+    e.get_attr("id") == Some("anchor") || e.get_attr((INKSCAPE_NS, "label")) == Some("anchor")
+}
+// the center of the element with the id or inkscape label "anchor", in the svg's user units
+fn find_anchor_marker(e: &elementtree::Element, outer: [f64; 6]) -> Result<Option<V2>, String> {
+    for c in e.children() {
+        let m = match c.get_attr("transform") {
+            Some(t) => compose(outer, parse_transform(t)?),
+            None => outer,
+        };
+        if is_anchor_marker(c) {
+            let n = |attr: &str| c.get_attr(attr).map_or(Ok(0.0), |v| {
+                v.trim().parse::<f64>().map_err(|_| format!("couldn't read the anchor marker's {attr}, {v:?}"))
+            });
+            let center = match c.tag().name() {
+                "circle" | "ellipse" => V2::new(n("cx")?, n("cy")?),
+                "rect" => V2::new(n("x")? + n("width")? / 2.0, n("y")? + n("height")? / 2.0),
+                other => {
+                    return Err(format!(
+                        "the anchor marker is a <{other}>, it should be a circle, ellipse or rect"
+                    ))
+                }
+            };
+            return Ok(Some(apply(m, center)));
+        }
+        if let Some(found) = find_anchor_marker(c, m)? {
+            return Ok(Some(found));
+        }
+    }
+    Ok(None)
+}
+fn remove_anchor_markers(e: &mut elementtree::Element) {
+    e.retain_children_mut(|c| {
+        remove_anchor_markers(c);
+        !is_anchor_marker(c)
+    });
+}
+
+// the anchor (the point that's placed at the position given to by_anchor, eg, a character's feet) can be given as anchor, or in the svg, as a data-anchor="x y" on the root, or as a small circle with the id or inkscape label "anchor" (which isn't drawn). Otherwise it's the center.
+fn asset_from_svg(assetxml: &elementtree::Element, name: &str, anchor: Option<V2>) -> Result<Asset, String> {
     //lol, turns out the comment isn't an element so the entire document is just the root element (what if a document contains multiple root elements? Is that not allowed?)
-    let svgel = &assetxml;
-    fn ignore_unit(v: &str) -> &str {
-        v.split_at(v.len() - 2).0
+    let mut svgel = assetxml.clone();
+    if svgel.tag().name() != "svg" {
+        return Err(format!("the root element is <{}>, not <svg>", svgel.tag().name()));
     }
-    let bounds = V2::new(
-        str::parse(ignore_unit(&svgel.get_attr("width").unwrap())).unwrap(),
-        str::parse(ignore_unit(&svgel.get_attr("height").unwrap())).unwrap(),
-    );
+    let width = svgel.get_attr("width").map(parse_length).transpose()?;
+    let height = svgel.get_attr("height").map(parse_length).transpose()?;
+    let view_box = svgel.get_attr("viewBox").map(parse_view_box).transpose()?;
+    // a missing dimension follows the viewBox's aspect, and with neither, the viewBox is taken to be in px, as browsers do
+    let bounds = match (width, height, &view_box) {
+        (Some(w), Some(h), _) => V2::new(w, h),
+        (Some(w), None, Some(vb)) => V2::new(w, w * vb.height() / vb.width()),
+        (None, Some(h), Some(vb)) => V2::new(h * vb.width() / vb.height(), h),
+        (None, None, Some(vb)) => V2::new(vb.width(), vb.height()) * (25.4 / 96.0),
+        (Some(_), None, None) => return Err("has a width but no height or viewBox".to_string()),
+        (None, Some(_), None) => return Err("has a height but no width or viewBox".to_string()),
+        (None, None, None) => {
+            return Err("has no width, height or viewBox, so there's no telling how big it is".to_string())
+        }
+    };
+    // user units to design units. Without a viewBox, user units are px.
+    let user_to_design = match &view_box {
+        Some(vb) => view_box_transform(vb, bounds, svgel.get_attr("preserveAspectRatio"))?,
+        None => [25.4 / 96.0, 0.0, 0.0, 25.4 / 96.0, 0.0, 0.0],
+    };
+    let declared_anchor = match svgel.get_attr("data-anchor") {
+        Some(v) => {
+            let ns: Vec<f64> = v
+                .split(|c: char| c.is_whitespace() || c == ',')
+                .filter(|s| !s.is_empty())
+                .map(|s| s.parse::<f64>())
+                .collect::<Result<_, _>>()
+                .map_err(|_| format!("couldn't read data-anchor {v:?}"))?;
+            match ns.as_slice() {
+                &[x, y] => Some(V2::new(x, y)),
+                _ => return Err(format!("data-anchor {v:?} should be two numbers, x y")),
+            }
+        }
+        None => find_anchor_marker(&svgel, [1.0, 0.0, 0.0, 1.0, 0.0, 0.0])?,
+    }
+    .map(|a| apply(user_to_design, a));
+    remove_anchor_markers(&mut svgel);
     let mut inner: Vec<u8> = Vec::new();
     for e in svgel.children() {
         e.to_writer_with_options(&mut inner, WriteOptions::new().set_xml_prolog(None))
             .unwrap();
     }
-    Asset {
+    let mut inner = String::from_utf8(inner).unwrap();
+    // most of our assets are drawn at 1 user unit per mm, those are left alone
+    let identity = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+    if user_to_design.iter().zip(identity.iter()).any(|(a, b)| (a - b).abs() > 1e-6) {
+        inner = format!("<g transform=\"{}\">{inner}</g>", Transform::Matrix(user_to_design));
+    }
+    let id = unique_id(&format!("asset_{name}_"));
+    let inner = Rc::new(namespace_ids(&inner, &format!("{id}-")).into_bytes());
+    Ok(Asset {
         render: Rc::new(
             move |ul: V2, scale: f64, rotation: f64, to: &mut dyn Write| {
                 write!(
@@ -1449,18 +2066,68 @@ pub fn load_asset(at: &Path, anchor: Option<V2>) -> Asset {
                     ul.x, ul.y
                 )
                 .unwrap();
-                to.write(&inner).unwrap();
+                if !use_symbol(&id, &inner, to) {
+                    to.write_all(&inner).unwrap();
+                }
                 write!(to, "</g>").unwrap();
             },
         ),
-        anchor: anchor.unwrap_or_else(|| bounds / 2.0),
+        anchor: anchor.or(declared_anchor).unwrap_or_else(|| bounds / 2.0),
         bounds,
+    })
+}
+
+// every svg in the asset directory, by its file name without the .svg. Overlay directories (a theme, an expansion) are scanned after it, and their svgs replace the ones with the same name, so an overlay only has to contain the graphics it changes. Assets are loaded the first time they're asked for.
+pub struct AssetRegistry {
+    files: HashMap<String, PathBuf>,
+    loaded: RefCell<HashMap<String, Asset>>,
+}
+impl AssetRegistry {
+    pub fn scan(dir: &Path, overlays: &[PathBuf]) -> Result<Self, String> {
+        let mut files = HashMap::new();
+        for d in iter::once(dir).chain(overlays.iter().map(|o| o.as_path())) {
+            let entries = std::fs::read_dir(d)
+                .map_err(|e| format!("couldn't read the asset directory {}, {e}", d.display()))?;
+            for entry in entries.flatten() {
+                let at = entry.path();
+                if at.extension().is_some_and(|e| e == "svg") {
+                    let name = at.file_stem().unwrap().to_string_lossy().into_owned();
+                    files.insert(name, at);
+                }
+            }
+        }
+        Ok(Self {
+            files,
+            loaded: RefCell::new(HashMap::new()),
+        })
+    }
+    // where the asset with this name comes from, after overlays
+    pub fn path(&self, name: &str) -> Option<&Path> {
+        self.files.get(name).map(|p| p.as_path())
+    }
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.files.keys().map(|n| n.as_str()).collect();
+        names.sort();
+        names
+    }
+    pub fn get(&self, name: &str) -> Result<Asset, String> {
+        if let Some(a) = self.loaded.borrow().get(name) {
+            return Ok(a.clone());
+        }
+        let at = self.files.get(name).ok_or_else(|| {
+            format!("there's no asset called {name:?}, there should be a {name}.svg in one of the asset directories")
+        })?;
+        let a = load_asset(at, None)?;
+        self.loaded.borrow_mut().insert(name.to_string(), a.clone());
+        Ok(a)
     }
 }
 
 //used to use macros here but macros in rust are just so shit
 pub struct Assets {
     pub kill: Asset,
+    pub hand: Asset,
+    pub heart: Asset,
     pub negatory: Asset,
     pub level1: Asset,
     pub level2: Asset,
@@ -1468,7 +2135,6 @@ pub struct Assets {
     pub guy: Asset,
     pub guy2: Asset,
     pub guy2_mage: Asset,
-    pub guy2_flipped: Asset,
     pub dead_guy2: Asset,
     pub cubed_guy2: Asset,
     pub guyeye: Asset,
@@ -1498,27 +2164,16 @@ pub struct Assets {
     pub double_diamond: Asset,
     pub end_top_bar: Asset,
     pub pnpmask: Asset,
+    pub pnpmask_low_ink: Asset,
 
-    pub field_forest: Asset,
-    pub mountain_volcano: Asset,
-    pub lake_ice: Asset,
-    pub tomb_void: Asset,
-
-    //means flip TO field
-    pub flip_field: Asset,
-    pub flip_forest: Asset,
-    pub flip_mountain: Asset,
-    pub flip_volcano: Asset,
-    pub flip_lake: Asset,
-    pub flip_ice: Asset,
-    pub flip_tomb: Asset,
-    pub flip_void: Asset,
+    // every svg in the asset directories, for anything that isn't one of the above
+    pub registry: AssetRegistry,
 
-    //means flipping either
-    pub flip_either_field_forest: Asset,
-    pub flip_either_mountain_volcano: Asset,
-    pub flip_either_tomb_void: Asset,
-    pub flip_either_lake_ice: Asset,
+    // the derived graphics are put together the first time they're drawn
+    guy2_flipped: OnceCell<Asset>,
+    element_both: [OnceCell<Asset>; 4],
+    flip_to: [OnceCell<Asset>; 8],
+    flip_either: [OnceCell<Asset>; 4],
 }
 
 fn generate_either(e1: &Asset, e2: &Asset) -> Asset {
@@ -1529,7 +2184,7 @@ fn generate_either(e1: &Asset, e2: &Asset) -> Asset {
             let e1 = e1.clone();
             let e2 = e2.clone();
             move |p, s, rotation, w| {
-                write!(w, r##"
+                write_namespaced("either", w, |w| write!(w, r##"
 <g transform="translate({},{}) scale({s}) rotate({rotation})">
 <defs
      id="eitherdefs">
@@ -1558,7 +2213,7 @@ fn generate_either(e1: &Asset, e2: &Asset) -> Asset {
                     p.x, p.y,
                     &Displaying(|w| e1.by_ul(both_dims(0.0), 1.0, w)),
                     &Displaying(|w| e2.by_ul(both_dims(0.0), 1.0, w)),
-                ).unwrap();
+                ).unwrap());
             }
         }),
     }
@@ -1582,7 +2237,6 @@ pub fn ring_conversion(
 <g
      inkscape:label="Layer 1"
      inkscape:groupmode="layer"
-     id="layer1"
      transform="translate({},{})">
     <g
        id="g3"
@@ -1625,84 +2279,113 @@ pub fn horizontal_flip(a: &Asset) -> Asset {
     }
 }
 
+// swaps one fill color for another, eg, to put a guy in a team's colors. The original asset only writes a <use> of its symbol, so the recolored content gets a symbol of its own, with its ids namespaced again so that it can sit in the same document as the original.
+pub fn recolored(a: &Asset, from: &str, to: &str) -> Asset {
+    let ac = a.clone();
+    let id = unique_id(&format!("recolored_{to}_"));
+    let from = format!("#{from}");
+    let to = format!("#{to}");
+    let content: OnceCell<Rc<Vec<u8>>> = OnceCell::new();
+    Asset {
+        bounds: a.bounds,
+        anchor: a.anchor,
+        render: Rc::new(move |p, scale, angle, w| {
+            let content = content.get_or_init(|| {
+                let mut inner = Vec::new();
+                without_symbols(|| (ac.render)(V2::zeros(), 1.0, 0.0, &mut inner));
+                let inner = String::from_utf8(inner).unwrap().replace(&from, &to);
+                Rc::new(namespace_ids(&inner, &format!("{id}-")).into_bytes())
+            });
+            write!(w, r#"<g transform="translate({},{}) scale({scale}) rotate({angle})">"#, p.x, p.y).unwrap();
+            if !use_symbol(&id, content, w) {
+                w.write_all(content).unwrap();
+            }
+            write!(w, "</g>").unwrap();
+        }),
+    }
+}
+
+// stands in for an asset that couldn't be loaded, so that loading can go on and find the rest of the problems
+fn placeholder_asset() -> Asset {
+    Asset {
+        render: Rc::new(|_, _, _, _| {}),
+        anchor: V2::new(0.5, 0.5),
+        bounds: V2::new(1.0, 1.0),
+    }
+}
+
 impl Assets {
-    pub fn load(_assets_dir: &Path) -> Self {
-        let kill = load_asset(&Path::new("assets/kill.svg"), None);
-        let negatory = load_asset(&Path::new("assets/negatory_shadowed.svg"), None);
-        let level2 = load_asset(&Path::new("assets/level_22.svg"), None);
-        let level1 = load_asset(&Path::new("assets/level1.svg"), None);
-        let clown = load_asset(&Path::new("assets/clown_in_diamond.svg"), None);
-        let guy = load_asset(&Path::new("assets/guy.svg"), None);
-        let guyeye: Asset = load_asset(&Path::new("assets/guyeye.svg"), None);
-        let dead_guy = load_asset(&Path::new("assets/dead_guy.svg"), None);
-        let altruism = load_asset(&Path::new("assets/altruism.svg"), None);
+    pub fn load(assets_dir: &Path) -> Self {
+        Self::load_with_overlays(assets_dir, &[])
+    }
+    // panics listing every asset that couldn't be loaded, rather than just the first
+    pub fn load_with_overlays(assets_dir: &Path, overlays: &[PathBuf]) -> Self {
+        let registry = AssetRegistry::scan(assets_dir, overlays).unwrap_or_else(|e| panic!("{e}"));
+        let mut problems: Vec<String> = Vec::new();
+        // anchors come from the svgs, see asset_from_svg
+        let mut load = |name: &str| {
+            registry.get(name).unwrap_or_else(|e| {
+                problems.push(e);
+                placeholder_asset()
+            })
+        };
+        let kill = load("kill");
+        let hand = load("hand");
+        let heart = load("heart");
+        let negatory = load("negatory_shadowed");
+        let level2 = load("level_22");
+        let level1 = load("level1");
+        let clown = load("clown_in_diamond");
+        let guy = load("guy");
+        let guyeye: Asset = load("guyeye");
+        let dead_guy = load("dead_guy");
+        let altruism = load("altruism");
         
-        let land_paths = LAND_THEME.borrow().asset_paths;
-        let field = load_asset(&Path::new(land_paths[0]), None);
-        let forest = load_asset(&Path::new(land_paths[1]), None);
-        let mountain = load_asset(&Path::new(land_paths[2]), None);
-        let volcano = load_asset(&Path::new(land_paths[3]), None);
-        let lake = load_asset(&Path::new(land_paths[4]), None);
-        let ice = load_asset(&Path::new(land_paths[5]), None);
-        let tomb = load_asset(&Path::new(land_paths[6]), None);
-        let void = load_asset(&Path::new(land_paths[7]), None);
+        let land_names = LAND_THEME.borrow().asset_names;
+        let field = with_element_mark(load(land_names[0]), FIELD);
+        let forest = with_element_mark(load(land_names[1]), FOREST);
+        let mountain = with_element_mark(load(land_names[2]), MOUNTAIN);
+        let volcano = with_element_mark(load(land_names[3]), VOLCANO);
+        let lake = with_element_mark(load(land_names[4]), LAKE);
+        let ice = with_element_mark(load(land_names[5]), ICE);
+        let tomb = with_element_mark(load(land_names[6]), TOMB);
+        let void = with_element_mark(load(land_names[7]), VOID);
         
-        let blank = load_asset(&Path::new("assets/blank.svg"), None);
-        let darker_blank = load_asset(&Path::new("assets/darker_blank.svg"), None);
-        let come_on_down = load_asset(&Path::new("assets/come_on_down.svg"), None);
-        let back_colored_circle = load_asset(&Path::new("assets/back_colored_circle.svg"), None);
-        let triangle = load_asset(
-            &Path::new("assets/triangle.svg"),
-            Some((V2::zeros() + V2::new(0.0, 1.0) + V2::new((3.0 / 4.0 as f64).sqrt(), 0.5)) / 3.0),
-        );
-        let end_top_bar = load_asset(&Path::new("assets/end_top_bar.svg"), None);
-        // let step = load_asset(&Path::new("assets/step.svg"), None);
-        let step = load_asset(&Path::new("assets/step2.svg"), None);
-        let dog_altruism = load_asset(&Path::new("assets/dog_altruism.svg"), None);
-        let guy2 = load_asset(&Path::new("assets/guy2_flat.svg"), Some(GUY2_ANCHOR));
-        // let guy2_mage = load_asset(
-        //     &Path::new("assets/guy2_mage_flat.svg"),
-        //     Some(V2::new(37.347, 82.709)),
-        // );
-        let guy2_mage = load_asset(
-            &Path::new("assets/guy2_mage_unfilled.svg"),
-            Some(V2::new(40.812, 86.348)),
-        );
-        let dead_guy2 = load_asset(&Path::new("assets/dead_guy2.svg"), Some(GUY2_DEAD_ANCHOR));
-        let cubed_guy2 = load_asset(
-            &Path::new("assets/cubed guy2.svg"),
-            Some(V2::new(27.316, 80.938)),
-        );
-        let kill_diamond = load_asset(&Path::new("assets/kill_diamond.svg"), None);
-        let kill_diamond_around = load_asset(&Path::new("assets/kill_diamond_around.svg"), None);
-        let double_diamond = load_asset(&Path::new("assets/double_diamond.svg"), None);
-
-        let guy2_flipped = horizontal_flip(&guy2);
-        let flip_field = element_flip(&forest, &field);
-        let flip_forest = element_flip(&field, &forest);
-        let flip_mountain = element_flip(&volcano, &mountain);
-        let flip_volcano = element_flip(&mountain, &volcano);
-        let flip_lake = element_flip(&ice, &lake);
-        let flip_ice = element_flip(&lake, &ice);
-        let flip_tomb = element_flip(&void, &tomb);
-        let flip_void = element_flip(&tomb, &void);
-
-        // let strikethrough_road = load_asset(&Path::new("assets/strikethrough_road.svg"), None);
-
-        let field_forest = generate_either(&field, &forest);
-        let mountain_volcano = generate_either(&mountain, &volcano);
-        let lake_ice = generate_either(&lake, &ice);
-        let tomb_void = generate_either(&tomb, &void);
-
-        let flip_either_field_forest =
-            element_flip(&field_forest, &generate_either(&forest, &field));
-        let flip_either_mountain_volcano =
-            element_flip(&mountain_volcano, &generate_either(&volcano, &mountain));
-        let flip_either_tomb_void = element_flip(&tomb_void, &generate_either(&void, &tomb));
-        let flip_either_lake_ice = element_flip(&lake_ice, &generate_either(&ice, &lake));
+        let blank = load("blank");
+        let darker_blank = load("darker_blank");
+        let come_on_down = load("come_on_down");
+        let back_colored_circle = load("back_colored_circle");
+        // anchored at its centroid
+        let triangle = load("triangle");
+        let end_top_bar = load("end_top_bar");
+        // let step = load("step");
+        let step = load("step2");
+        let dog_altruism = load("dog_altruism");
+        let guy2 = load("guy2_flat");
+        let guy2_mage = load("guy2_mage_unfilled");
+        let dead_guy2 = load("dead_guy2");
+        let cubed_guy2 = load("cubed guy2");
+        let kill_diamond = load("kill_diamond");
+        let kill_diamond_around = load("kill_diamond_around");
+        let double_diamond = load("double_diamond");
+        let interventionist_helix = load("interventionist helix");
+        let grouping1 = load("grouping1");
+        let grouping2 = load("grouping2");
+        let grouping3 = load("grouping3");
+        let pnpmask = load("pnpmask");
+        let pnpmask_low_ink = load("pnpmask_low_ink");
+        if !problems.is_empty() {
+            panic!(
+                "{} assets couldn't be loaded:\n{}",
+                problems.len(),
+                problems.join("\n")
+            );
+        }
 
         Self {
             kill,
+            hand,
+            heart,
             negatory,
             level1,
             level2,
@@ -1711,8 +2394,7 @@ impl Assets {
             guy2,
             kill_diamond_around,
             guy2_mage,
-            interventionist_helix: load_asset(Path::new("assets/interventionist helix.svg"), None),
-            guy2_flipped,
+            interventionist_helix,
             dead_guy2,
             guyeye,
             dead_guy,
@@ -1722,9 +2404,9 @@ impl Assets {
             triangle,
             forest,
             mountain,
-            grouping1: load_asset(&Path::new("assets/grouping1.svg"), Some(V2::new(18.065, 18.065))),
-            grouping2: load_asset(&Path::new("assets/grouping2.svg"), Some(V2::new(18.065, 18.065))),
-            grouping3: load_asset(&Path::new("assets/grouping3.svg"), Some(V2::new(35.085, 47.547))),
+            grouping1,
+            grouping2,
+            grouping3,
             volcano,
             lake,
             double_diamond,
@@ -1734,28 +2416,57 @@ impl Assets {
             void,
             blank,
             come_on_down,
-            pnpmask: load_asset(Path::new("assets/pnpmask.svg"), None),
+            pnpmask,
+            pnpmask_low_ink,
             back_colored_circle,
             end_top_bar,
             step,
             dog_altruism,
-            flip_field,
-            flip_forest,
-            flip_mountain,
-            flip_volcano,
-            flip_lake,
-            flip_ice,
-            flip_tomb,
-            flip_void,
-            field_forest,
-            mountain_volcano,
-            lake_ice,
-            tomb_void,
             kill_diamond,
-            flip_either_field_forest,
-            flip_either_mountain_volcano,
-            flip_either_tomb_void,
-            flip_either_lake_ice,
+            registry,
+            guy2_flipped: OnceCell::new(),
+            element_both: Default::default(),
+            flip_to: Default::default(),
+            flip_either: Default::default(),
+        }
+    }
+    // any asset by name, for the graphics that don't have a field
+    pub fn named(&self, name: &str) -> Asset {
+        self.registry.get(name).unwrap_or_else(|e| panic!("{e}"))
+    }
+    // covers the bleed around each card on the print and play sheets. The svgs are drawn for mini cards, other formats get one drawn to fit.
+    pub fn pnp_mask(&self, low_ink: bool) -> Asset {
+        if card_format() == MINI_CARDS {
+            return if low_ink { self.pnpmask_low_ink.clone() } else { self.pnpmask.clone() };
+        }
+        let color = if low_ink { "ffffff" } else { "f1f2f2" };
+        let dims = card_dimensions();
+        let size = card_format().print_dimensions();
+        let svg = format!(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="{}mm" height="{}mm" viewBox="0 0 {} {}"><path style="fill:#{color};fill-rule:evenodd" d="M 0,0 V {} H {} V 0 Z {}" /></svg>"##,
+            size.x,
+            size.y,
+            dims.x,
+            dims.y,
+            dims.y,
+            dims.x,
+            rounded_rect_path(&cutline_bounds(), 19.531625)
+        );
+        asset_from_svg_data(svg.as_bytes(), "pnpmask", None)
+    }
+    pub fn guy2_flipped(&self) -> &Asset {
+        self.guy2_flipped.get_or_init(|| horizontal_flip(&self.guy2))
+    }
+    // the icons that can be written inline in card text as {name}
+    pub fn icon(&self, name: &str) -> Option<&Asset> {
+        if let Some(e) = ELEMENT_NAMES.iter().position(|n| *n == name) {
+            return Some(self.element(e));
+        }
+        match name {
+            "kill" => Some(&self.kill),
+            "hand" => Some(&self.hand),
+            "heart" => Some(&self.heart),
+            _ => None,
         }
     }
     pub fn element(&self, e: ElementTag) -> &Asset {
@@ -1772,44 +2483,30 @@ impl Assets {
         }
     }
     pub fn element_both(&self, e: ElementTag) -> &Asset {
-        match e {
-            FIELD => &self.field_forest,
-            MOUNTAIN => &self.mountain_volcano,
-            LAKE => &self.lake_ice,
-            TOMB => &self.tomb_void,
-            _ => panic!(
-                "{} is an invalid tag for a pair of elements",
-                ELEMENT_NAMES[e]
-            ),
+        if e >= 8 || e % 2 == 1 {
+            panic!("{} is an invalid tag for a pair of elements", ELEMENT_NAMES[e]);
         }
+        self.element_both[e / 2]
+            .get_or_init(|| generate_either(self.element(e), self.element(e + 1)))
     }
     pub fn flip_to(&self, e: ElementTag) -> &Asset {
-        match e {
-            //means flip TO field
-            FIELD => &self.flip_field,
-            FOREST => &self.flip_forest,
-            LAKE => &self.flip_lake,
-            ICE => &self.flip_ice,
-            TOMB => &self.flip_tomb,
-            VOID => &self.flip_void,
-            MOUNTAIN => &self.flip_mountain,
-            VOLCANO => &self.flip_volcano,
-            _ => panic!("{e} is not an element tag"),
+        if e >= 8 {
+            panic!("{e} is not an element tag");
         }
+        //means flip TO e
+        self.flip_to[e].get_or_init(|| element_flip(self.element(opposite_element(e)), self.element(e)))
     }
     pub fn flip_either(&self, e: ElementTag) -> &Asset {
-        match e {
-            //means flip TO field
-            FIELD => &self.flip_either_field_forest,
-            FOREST => &self.flip_either_field_forest,
-            LAKE => &self.flip_either_lake_ice,
-            ICE => &self.flip_either_lake_ice,
-            TOMB => &self.flip_either_tomb_void,
-            VOID => &self.flip_either_tomb_void,
-            MOUNTAIN => &self.flip_either_mountain_volcano,
-            VOLCANO => &self.flip_either_mountain_volcano,
-            _ => panic!("{e} is not an element tag"),
+        if e >= 8 {
+            panic!("{e} is not an element tag");
         }
+        let first = e - e % 2;
+        self.flip_either[e / 2].get_or_init(|| {
+            element_flip(
+                self.element_both(first),
+                &generate_either(self.element(first + 1), self.element(first)),
+            )
+        })
     }
 }
 
@@ -1830,6 +2527,246 @@ pub fn guylike(asset: &Asset, base_centered: V2, scale: f64, to: &mut dyn Write)
     asset.by_anchor(base_centered, scale, to);
 }
 
+// how card_outer and end_outer draw the card around its contents. Print vendors each want a different amount of bleed.
+#[derive(Clone, Copy, Debug)]
+pub struct CardPrintConf {
+    // bleed beyond the 1/8in that's already there, in printed design units. The card is mirrored out into it, which extends the background and anything touching the edge. Can't be more than another 1/8in, since that's all the mirror has to draw from.
+    pub extra_bleed: f64,
+    // draws the cutline and the safe zone over the card, shading everything outside of the safe zone, so you can see what's at risk of being cut
+    pub safe_zone_overlay: bool,
+    // for home printers: white backgrounds, the card's shape outlined instead of filled, a lighter end bar, and no blurred front on the backs
+    pub low_ink: bool,
+}
+impl Default for CardPrintConf {
+    fn default() -> Self {
+        Self {
+            extra_bleed: 0.0,
+            safe_zone_overlay: false,
+            low_ink: false,
+        }
+    }
+}
+thread_local! {
+    static CARD_PRINT_CONF: Cell<CardPrintConf> = Cell::new(CardPrintConf::default());
+}
+pub fn set_card_print_conf(conf: CardPrintConf) {
+    let most = BLEED_INCHES * DESIGN_UNITS_PER_INCH;
+    assert!(
+        (0.0..=most).contains(&conf.extra_bleed),
+        "extra_bleed has to be between 0 and {most}"
+    );
+    CARD_PRINT_CONF.with(|c| c.set(conf));
+}
+pub fn card_print_conf() -> CardPrintConf {
+    CARD_PRINT_CONF.with(|c| c.get())
+}
+// for exporters that need a print conf of their own, eg, with the usual bleed. Puts back whichever conf was set before once f is done.
+// overflows are reported when the card svgs are written (see gen_cards), so whatever f lays out just drops its own, rather than having them blamed on whichever card is written next.
+pub fn with_card_print_conf<R>(conf: CardPrintConf, f: impl FnOnce() -> R) -> R {
+    let previous = card_print_conf();
+    set_card_print_conf(conf);
+    let r = f();
+    take_text_overflows();
+    set_card_print_conf(previous);
+    r
+}
+
+const LOW_INK_BACKGROUND_COLOR: &str = "ffffff";
+const LOW_INK_OUTLINE_COLOR: &str = "c8c8c8";
+// what a card's background is actually filled with
+pub fn printed_background(color: &str) -> &str {
+    if card_print_conf().low_ink {
+        LOW_INK_BACKGROUND_COLOR
+    } else {
+        color
+    }
+}
+// for the rounded card shape inside the bleed
+fn cutline_style(background_color: &str) -> String {
+    if card_print_conf().low_ink {
+        format!("fill:none;stroke:#{LOW_INK_OUTLINE_COLOR};stroke-width:0.6")
+    } else {
+        format!("fill:#{background_color};fill-opacity:1;stroke:none")
+    }
+}
+
+// the card's background, the points are scaled up by CARD_BACKGROUND_TRANSFORM
+fn card_background_points() -> String {
+    let h = 198.0 + extra_layout_height() / 1.1024306;
+    format!("144,{h} 144,0 0,0 0,{h}")
+}
+fn cutline_path() -> String {
+    rounded_rect_path(&cutline_bounds(), cutline_inset().x)
+}
+// the extra bleed in layout units
+fn extra_bleed() -> f64 {
+    card_print_conf().extra_bleed / card_format().print_scale()
+}
+// the root of a document pasted from inkscape, with its width, height and viewBox
+fn inkscape_svg(width: String, height: String, view_box: String) -> Element {
+    el("svg")
+        .attr("width", width)
+        .attr("height", height)
+        .attr("viewBox", view_box)
+        .attr("version", "1.1")
+        .id("svg1")
+        .attr("inkscape:version", "1.3.1 (91b66b0783, 2023-11-16)")
+        .attr("sodipodi:docname", "card front template.svg")
+        .attr("xmlns:inkscape", "http://www.inkscape.org/namespaces/inkscape")
+        .attr("xmlns:sodipodi", "http://sodipodi.sourceforge.net/DTD/sodipodi-0.dtd")
+        .attr("xmlns", "http://www.w3.org/2000/svg")
+        .attr("xmlns:svg", "http://www.w3.org/2000/svg")
+}
+fn inkscape_namedview() -> Element {
+    el("sodipodi:namedview")
+        .id("namedview1")
+        .attr("pagecolor", "#ffffff")
+        .attr("bordercolor", "#000000")
+        .attr("borderopacity", "0.25")
+        .attr("inkscape:showpageshadow", "2")
+        .attr("inkscape:pageopacity", "0.0")
+        .attr("inkscape:pagecheckerboard", "0")
+        .attr("inkscape:deskcolor", "#d1d1d1")
+        .attr("inkscape:document-units", "mm")
+        .attr("inkscape:zoom", "0.64462111")
+        .attr("inkscape:cx", "197.79061")
+        .attr("inkscape:cy", "62.827604")
+        .attr("inkscape:window-width", "1876")
+        .attr("inkscape:window-height", "1032")
+        .attr("inkscape:window-x", "44")
+        .attr("inkscape:window-y", "0")
+        .attr("inkscape:window-maximized", "1")
+        .attr("inkscape:current-layer", "layer1")
+}
+fn write_document(comment: &str, svg: &Element, to: &mut dyn Write) {
+    write!(
+        to,
+        r##"<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<!-- {comment} -->
+
+{svg}
+"##
+    )
+    .unwrap();
+}
+
+// a card's svg root, grown by the extra bleed. The viewBox is in layout units, the size is the card format's.
+fn card_svg() -> Element {
+    let b = extra_bleed();
+    let span = card_dimensions() + both_dims(2.0 * b);
+    let size = span * card_format().print_scale();
+    let o = 0.0 - b;
+    inkscape_svg(
+        format!("{}mm", size.x),
+        format!("{}mm", size.y),
+        format!("{o} {o} {} {}", span.x, span.y),
+    )
+}
+
+// drawn over a card's layer (which has to have the id g8), see CardPrintConf
+fn card_extensions() -> Vec<Node> {
+    let conf = card_print_conf();
+    let mut r = Vec::new();
+    let b = extra_bleed();
+    if b > 0.0 {
+        let dims = card_dimensions();
+        // each of the eight strips around the card shows the card reflected over the edges it's beyond
+        let mut i = 0;
+        for sy in [-1, 0, 1] {
+            for sx in [-1, 0, 1] {
+                if sx == 0 && sy == 0 {
+                    continue;
+                }
+                let range = |s: i32, span: f64| match s {
+                    -1 => (-b, 0.0),
+                    0 => (0.0, span),
+                    _ => (span, span + b),
+                };
+                let mirror = |s: i32, span: f64| match s {
+                    -1 => (-1.0, 0.0),
+                    0 => (1.0, 0.0),
+                    _ => (-1.0, 2.0 * span),
+                };
+                let (x0, x1) = range(sx, dims.x);
+                let (y0, y1) = range(sy, dims.y);
+                let (a, e) = mirror(sx, dims.x);
+                let (d, f) = mirror(sy, dims.y);
+                let clip = format!("bleedclip{i}");
+                r.push(
+                    el("clipPath")
+                        .id(&clip)
+                        .child(rect(V2::new(x0, y0), V2::new(x1 - x0, y1 - y0)))
+                        .into(),
+                );
+                r.push(
+                    g().attr("clip-path", format!("url(#{clip})"))
+                        .child(
+                            el("use")
+                                .attr("href", "#g8")
+                                .transform(&[Transform::Matrix([a, 0.0, 0.0, d, e, f])]),
+                        )
+                        .into(),
+                );
+                i += 1;
+            }
+        }
+    }
+    if conf.safe_zone_overlay {
+        let cut = cutline_bounds();
+        let safe = cut.reduced_by(SAFE_ZONE_INSET / card_format().print_scale());
+        let outer = Rect {
+            ul: both_dims(-b),
+            br: card_dimensions() + both_dims(b),
+        };
+        let rect_path = |r: &Rect| {
+            format!(
+                "M {},{} H {} V {} H {} Z",
+                r.ul.x, r.ul.y, r.br.x, r.br.y, r.ul.x
+            )
+        };
+        r.push(
+            path(&format!("{} {}", rect_path(&outer), rect_path(&safe)))
+                .style("fill:#ff0000;fill-opacity:0.2;fill-rule:evenodd;stroke:none")
+                .into(),
+        );
+        r.push(
+            rect(cut.ul, cut.span())
+                .attr("rx", cutline_inset().x)
+                .style("fill:none;stroke:#ff0000;stroke-width:0.8;stroke-dasharray:3,2")
+                .into(),
+        );
+        r.push(
+            rect(safe.ul, safe.span())
+                .style("fill:none;stroke:#0070ff;stroke-width:0.8;stroke-dasharray:3,2")
+                .into(),
+        );
+    }
+    r
+}
+
+// the card's layer, its background and cutline, then the content. The extensions refer to it as g8.
+fn card_layer(background_color: &str) -> Element {
+    let background_color = printed_background(background_color);
+    layer()
+        .id("g8")
+        .transform(&[Transform::Translate(V2::new(0.0, 0.0))])
+        .child(
+            polygon(&card_background_points())
+                .attr("fill", "#929497")
+                .id("assetback")
+                .transform(&[CARD_BACKGROUND_TRANSFORM])
+                .style(format!("fill:#{background_color};fill-opacity:1;stroke-width:0.24")),
+        )
+        .child(
+            path(&cutline_path())
+                .attr("fill", "#ffffff")
+                .attr("stroke", "#ec1e28")
+                .attr("stroke-width", "0.374174")
+                .id("cutline")
+                .style(cutline_style(background_color)),
+        )
+}
+
 pub fn blank_front(inserting: &impl Display, color: &str, rotate: bool, to: &mut dyn Write) {
     card_outer(inserting, "", color, rotate, to);
 }
@@ -1843,168 +2780,50 @@ pub fn card_outer(
     rotate: bool,
     to: &mut dyn Write,
 ) {
-    let rotation = if rotate { "90" } else { "0" };
-    write!(to, r##"<?xml version="1.0" encoding="UTF-8" standalone="no"?>
-<!-- Created with Inkscape (http://www.inkscape.org/) and also with mako -->
-
-<svg
-   width="158.75mm"
-   height="218.28127mm"
-   viewBox="0 0 158.75 218.28127"
-   version="1.1"
-   id="svg1"
-   inkscape:version="1.3.1 (91b66b0783, 2023-11-16)"
-   sodipodi:docname="card front template.svg"
-   xmlns:inkscape="http://www.inkscape.org/namespaces/inkscape"
-   xmlns:sodipodi="http://sodipodi.sourceforge.net/DTD/sodipodi-0.dtd"
-   xmlns="http://www.w3.org/2000/svg"
-   xmlns:svg="http://www.w3.org/2000/svg">
-  <sodipodi:namedview
-     id="namedview1"
-     pagecolor="#ffffff"
-     bordercolor="#000000"
-     borderopacity="0.25"
-     inkscape:showpageshadow="2"
-     inkscape:pageopacity="0.0"
-     inkscape:pagecheckerboard="0"
-     inkscape:deskcolor="#d1d1d1"
-     inkscape:document-units="mm"
-     inkscape:zoom="0.64462111"
-     inkscape:cx="197.79061"
-     inkscape:cy="62.827604"
-     inkscape:window-width="1876"
-     inkscape:window-height="1032"
-     inkscape:window-x="44"
-     inkscape:window-y="0"
-     inkscape:window-maximized="1"
-     inkscape:current-layer="layer1" />
-  <defs
-     id="defsbasic">
-    <rect
-       x="40.66116"
-       y="678.98822"
-       width="512.60158"
-       height="111.65349"
-       id="rect10" />
-    <rect
-       x="442.54103"
-       y="764.68524"
-       width="32.549689"
-       height="18.533424"
-       id="rect9" />
-    </defs>
-  <g
-     inkscape:label="Layer 1"
-     inkscape:groupmode="layer"
-     id="g8"
-     transform="translate(0,0)">
-    <polygon
-       fill="#929497"
-       points="144,198 144,0 0,0 0,198 "
-       id="assetback"
-       transform="matrix(1.1024306,0,0,1.1024306,0,2e-4)"
-       style="fill:#{background_color};fill-opacity:1;stroke-width:0.24" />
-    <path
-       fill="#ffffff"
-       stroke="#ec1e28"
-       stroke-width="0.374174"
-       d="M 138.9062,208.35959 H 19.8437 c -5.4799,0 -9.9221,-4.4417 -9.9221,-9.9219 V 19.844 c 0,-5.4802 4.4422,-9.9219 9.9221,-9.9219 h 119.0625 c 5.48,0 9.9218,4.4417 9.9218,9.9219 v 178.59369 c 0,5.4802 -4.4418,9.9219 -9.9218,9.9219 z"
-       id="cutline"
-       style="fill:#{background_color};fill-opacity:1;stroke:none" />
-    <g
-       transform="translate(84.75197610056054,103.4528234964666) scale(0.5)"
-       id="g1">
-      <g
-         inkscape:label="Layer 1"
-         inkscape:groupmode="layer"
-         id="layer1"
-         transform="translate(-1066.7783,-589.34825)" />
-    </g>
-    <g transform="rotate({rotation},79.375003,109.14083)">
-    {inserting}
-    </g>
-    <text
-       xml:space="preserve"
-       transform="matrix(0.26458333,0,0,0.26458333,0.55598493,-3.6076306)"
-       id="text10"
-       style="font-style:normal;font-variant:normal;font-weight:normal;font-stretch:normal;font-size:53.3333px;line-height:1.05;font-family:Rubik;-inkscape-font-specification:Rubik;text-align:center;white-space:pre;shape-inside:url(#rect10);display:inline;fill:#757575;fill-opacity:1;stroke:none;stroke-width:7.55906;stroke-linecap:round;stroke-linejoin:round"><tspan
-         x="84.767989"
-         y="722.40316"
-         id="tspan3">{name}</tspan></text>
-  </g>
-</svg>
-"##).unwrap();
+    let rotation = if rotate { 90.0 } else { 0.0 };
+    // the name sits along the bottom, centered on where inkscape's text box for it used to be (x 40.66 width 512.6, before the transform)
+    let name_y = -3.6076306 + extra_layout_height();
+    begin_symbols();
+    // before the SymbolDefs, so that they know what was used
+    let content = g()
+        .transform(&[Transform::RotateAbout(rotation, card_dimensions() / 2.0)])
+        .child(raw_display(inserting));
+    let svg = card_svg()
+        .child(inkscape_namedview())
+        .child(
+            el("defs").id("defsbasic").child(
+                rect(V2::new(442.54103, 764.68524), V2::new(32.549689, 18.533424)).id("rect9"),
+            ),
+        )
+        .child(
+            card_layer(background_color)
+                .child(content)
+                .child(raw_display(&SymbolDefs))
+                .child(
+                    el("text")
+                        .attr("xml:space", "preserve")
+                        .transform(&[Transform::Matrix([0.26458333, 0.0, 0.0, 0.26458333, 0.55598493, name_y])])
+                        .id("text10")
+                        .style("font-style:normal;font-variant:normal;font-weight:normal;font-stretch:normal;font-size:53.3333px;line-height:1.05;font-family:Rubik;-inkscape-font-specification:Rubik;text-align:center;text-anchor:middle;white-space:pre;display:inline;fill:#757575;fill-opacity:1;stroke:none;stroke-width:7.55906;stroke-linecap:round;stroke-linejoin:round")
+                        .child(tspan(V2::new(296.96195, 722.40316), name).id("tspan3")),
+                ),
+        )
+        .children(card_extensions());
+    write_document("Created with Inkscape (http://www.inkscape.org/) and also with mako", &svg, to);
 }
 
 pub fn end_outer(inserting: &impl Display, to: &mut dyn Write) {
-    let background_color = "f1f2f2";
-    write!(to, r##"<?xml version="1.0" encoding="UTF-8" standalone="no"?>
-<!-- Created with Inkscape (http://www.inkscape.org/) and also with mako -->
-
-<svg
-   width="158.75mm"
-   height="218.28127mm"
-   viewBox="0 0 158.75 218.28127"
-   version="1.1"
-   id="svg1"
-   inkscape:version="1.3.1 (91b66b0783, 2023-11-16)"
-   sodipodi:docname="card front template.svg"
-   xmlns:inkscape="http://www.inkscape.org/namespaces/inkscape"
-   xmlns:sodipodi="http://sodipodi.sourceforge.net/DTD/sodipodi-0.dtd"
-   xmlns="http://www.w3.org/2000/svg"
-   xmlns:svg="http://www.w3.org/2000/svg">
-  <sodipodi:namedview
-     id="namedview1"
-     pagecolor="#ffffff"
-     bordercolor="#000000"
-     borderopacity="0.25"
-     inkscape:showpageshadow="2"
-     inkscape:pageopacity="0.0"
-     inkscape:pagecheckerboard="0"
-     inkscape:deskcolor="#d1d1d1"
-     inkscape:document-units="mm"
-     inkscape:zoom="0.64462111"
-     inkscape:cx="197.79061"
-     inkscape:cy="62.827604"
-     inkscape:window-width="1876"
-     inkscape:window-height="1032"
-     inkscape:window-x="44"
-     inkscape:window-y="0"
-     inkscape:window-maximized="1"
-     inkscape:current-layer="layer1" />
-  <g
-     inkscape:label="Layer 1"
-     inkscape:groupmode="layer"
-     id="g8"
-     transform="translate(0,0)">
-    <polygon
-       fill="#929497"
-       points="144,198 144,0 0,0 0,198 "
-       id="assetback"
-       transform="matrix(1.1024306,0,0,1.1024306,0,2e-4)"
-       style="fill:#{background_color};fill-opacity:1;stroke-width:0.24" />
-    <path
-       fill="#ffffff"
-       stroke="#ec1e28"
-       stroke-width="0.374174"
-       d="M 138.9062,208.35959 H 19.8437 c -5.4799,0 -9.9221,-4.4417 -9.9221,-9.9219 V 19.844 c 0,-5.4802 4.4422,-9.9219 9.9221,-9.9219 h 119.0625 c 5.48,0 9.9218,4.4417 9.9218,9.9219 v 178.59369 c 0,5.4802 -4.4418,9.9219 -9.9218,9.9219 z"
-       id="cutline"
-       style="fill:#{background_color};fill-opacity:1;stroke:none" />
-    <g
-       transform="translate(84.75197610056054,103.4528234964666) scale(0.5)"
-       id="g1">
-      <g
-         inkscape:label="Layer 1"
-         inkscape:groupmode="layer"
-         id="layer1"
-         transform="translate(-1066.7783,-589.34825)" />
-    </g>
-    <g>
-        {inserting}
-    </g>
-  </g>
-</svg>
-"##).unwrap();
+    begin_symbols();
+    let content = g().child(raw_display(inserting));
+    let svg = card_svg()
+        .child(inkscape_namedview())
+        .child(
+            card_layer(CARD_BACKGROUND_COLOR)
+                .child(content)
+                .child(raw_display(&SymbolDefs)),
+        )
+        .children(card_extensions());
+    write_document("Created with Inkscape (http://www.inkscape.org/) and also with mako", &svg, to);
 }
 
 pub fn flip_rings(
@@ -2017,24 +2836,25 @@ pub fn flip_rings(
     to: &mut dyn Write,
 ) {
     let offset = center - rotate(from_angle(rotation), both_dims(FLIP_RINGS_RAD)) * scale;
-    write!(to, r##"<g
-        inkscape:label="Layer 1"
-        inkscape:groupmode="layer"
-        id="layer1"
-        transform="translate({},{}) scale({}) rotate({})">
-        <circle
-        style="fill:#{to_color};stroke-width:2;stroke-linecap:round;stroke-linejoin:round"
-        id="path1"
-        cx="57.828403"
-        cy="57.828403"
-        r="57.828403" />
-        {element_graphic}
-        <path
-        id="circle1"
-        style="fill:#{from_color};stroke-width:2;stroke-linecap:round;stroke-linejoin:round;fill-opacity:1"
-        d="m 0,57.828512 c -5.7990107e-5,31.937829 25.890683,57.828568 57.828512,57.828508 31.937827,6e-5 57.828568,-25.890681 57.828508,-57.828508 H 102.70071 C 102.70061,82.610703 82.610703,102.70061 57.828512,102.70071 33.04612,102.7009 12.955894,82.610904 12.955798,57.828512 Z"
-        sodipodi:nodetypes="ccccccc" />
-    </g>"##, offset.x, offset.y, scale, rotation/TAU*360.0).unwrap();
+    let rings = layer()
+        .transform(&[
+            Transform::Translate(offset),
+            Transform::Scale(scale),
+            Transform::Rotate(rotation / TAU * 360.0),
+        ])
+        .child(
+            circle(both_dims(57.828403), 57.828403)
+                .style(format!("fill:#{to_color};stroke-width:2;stroke-linecap:round;stroke-linejoin:round"))
+                .id("path1"),
+        )
+        .child(raw_display(element_graphic))
+        .child(
+            path("m 0,57.828512 c -5.7990107e-5,31.937829 25.890683,57.828568 57.828512,57.828508 31.937827,6e-5 57.828568,-25.890681 57.828508,-57.828508 H 102.70071 C 102.70061,82.610703 82.610703,102.70061 57.828512,102.70071 33.04612,102.7009 12.955894,82.610904 12.955798,57.828512 Z")
+                .id("circle1")
+                .style(format!("fill:#{from_color};stroke-width:2;stroke-linecap:round;stroke-linejoin:round;fill-opacity:1"))
+                .attr("sodipodi:nodetypes", "ccccccc"),
+        );
+    write_namespaced("flip_rings", to, |to| rings.write_to(to));
 }
 
 pub fn chain_graphic(
@@ -2062,7 +2882,6 @@ pub fn chain_graphic(
 <g
      inkscape:label="Layer 1"
      inkscape:groupmode="layer"
-     id="layer1"
      transform="translate({},{}), scale({scale})"
      >
     <path
@@ -2127,7 +2946,6 @@ pub fn joined_pair_graphic_horizontal(
 <g
      inkscape:label="Layer 1"
      inkscape:groupmode="layer"
-     id="layer1"
      transform="translate({},{}), scale({scale})"
      >
     <path
@@ -2193,7 +3011,6 @@ pub fn joined_pair_verticalish(
         r##"<g
      inkscape:label="Layer 1"
      inkscape:groupmode="layer"
-     id="layer1"
      transform="translate({},{}) scale({s})"
      >
     <path
@@ -2263,7 +3080,6 @@ pub fn dual_color_patch(
         r##"<g
      inkscape:label="Layer 1"
      inkscape:groupmode="layer"
-     id="layer1"
      transform="translate({},{}) scale({})">
     <path
        id="path27"
@@ -2309,7 +3125,6 @@ pub fn come_on_down_specifically(
     write!(to, r##"<g
      inkscape:label="Layer 1"
      inkscape:groupmode="layer"
-     id="layer1"
      transform="translate({},{}) scale({codscale})">
     <path
        id="path50"
@@ -2358,50 +3173,32 @@ pub fn overplace(
 pub fn svg_outer(span: V2, background_color: &str, inserting: &impl Display, to: &mut dyn Write) {
     let span_x = span.x;
     let span_y = span.y;
-    write!(
-        to,
-        r##"<?xml version="1.0" encoding="UTF-8" standalone="no"?>
-<!-- Created partially with Inkscape (http://www.inkscape.org/) but primarily through codegen -->
-
-<svg
-   width="{span_x}mm"
-   height="{span_y}mm"
-   viewBox="0 0 {span_x} {span_y}"
-   version="1.1"
-   id="svg1"
-   inkscape:version="1.3.1 (91b66b0783, 2023-11-16)"
-   sodipodi:docname="card front template.svg"
-   xmlns:inkscape="http://www.inkscape.org/namespaces/inkscape"
-   xmlns:sodipodi="http://sodipodi.sourceforge.net/DTD/sodipodi-0.dtd"
-   xmlns="http://www.w3.org/2000/svg"
-   xmlns:svg="http://www.w3.org/2000/svg">
-  <sodipodi:namedview
-     id="namedview1"
-     pagecolor="#ffffff"
-     bordercolor="#000000"
-     borderopacity="0.25"
-     inkscape:showpageshadow="2"
-     inkscape:pageopacity="1.0"
-     inkscape:pagecheckerboard="0"
-     inkscape:deskcolor="#d1d1d1"
-     inkscape:document-units="mm"
-     inkscape:zoom="0.64462111"
-     inkscape:cx="197.79061"
-     inkscape:cy="62.827604"
-     inkscape:window-width="{span_x}"
-     inkscape:window-height="{span_y}"
-     inkscape:window-x="44"
-     inkscape:window-y="0"
-     inkscape:window-maximized="1"
-     inkscape:current-layer="layer1" />
-  <defs
-     id="defs1" />
-     <polygon fill="#{background_color}" points="0,0 0,{span_y} {span_x},{span_y} {span_x},0 0,0 "/>
-  {inserting}
-</svg>
-"##,
+    begin_symbols();
+    // before the SymbolDefs, so that they know what was used
+    let content = raw_display(inserting);
+    let svg = inkscape_svg(
+        format!("{span_x}mm"),
+        format!("{span_y}mm"),
+        format!("0 0 {span_x} {span_y}"),
     )
-    .unwrap();
+    .child(
+        inkscape_namedview()
+            .attr("inkscape:pageopacity", "1.0")
+            .attr("inkscape:window-width", span_x)
+            .attr("inkscape:window-height", span_y),
+    )
+    .child(el("defs").id("defs1"))
+    .child(
+        polygon(&format!("0,0 0,{span_y} {span_x},{span_y} {span_x},0 0,0 "))
+            .attr("fill", format!("#{background_color}")),
+    )
+    .child(content)
+    .child(raw_display(&SymbolDefs));
+    write_document(
+        "Created partially with Inkscape (http://www.inkscape.org/) but primarily through codegen",
+        &svg,
+        to,
+    );
 }
 
 pub fn do_sheet(span: V2, inserting: &impl Display, to: &mut dyn Write) {
@@ -2427,7 +3224,7 @@ fn element_flip(from: &Asset, to: &Asset) -> Asset {
                 //     w,
                 // );
 
-                write!(
+                write_namespaced("flip", w, |w| write!(
                     w,
                     r##"
 <g transform="translate({},{}) scale({s})">
@@ -2455,7 +3252,6 @@ fn element_flip(from: &Asset, to: &Asset) -> Asset {
   <g
      inkscape:label="Layer 1"
      inkscape:groupmode="layer"
-     id="layer1"
      transform="translate(3.5032504e-4,-4.5292512e-5)">
     <g
        id="g4"
@@ -2473,7 +3269,7 @@ fn element_flip(from: &Asset, to: &Asset) -> Asset {
                     p.x, p.y,
                     &Displaying(|w| to.by_ul(V2::new(0.0, 0.0), 1.0, w)),
                     &Displaying(|w| from.by_ul(V2::new(0.0, 0.0), 1.0, w)),
-                ).unwrap();
+                ).unwrap());
             }
         }),
     }
@@ -2492,7 +3288,7 @@ pub fn guy2(assets: &Rc<Assets>, c: V2, scale: f64, w: &mut dyn Write) {
 }
 
 pub fn guy2_flipped(assets: &Rc<Assets>, c: V2, scale: f64, w: &mut dyn Write) {
-    assets.guy2_flipped.by_anchor(c, scale, w);
+    assets.guy2_flipped().by_anchor(c, scale, w);
 }
 
 // I was going to use constraint satisfication, but I think these constraints are all just ratios
@@ -2581,36 +3377,212 @@ impl FinalGenConf {
 pub struct PnpGen {
     pub gen_svgs: bool,
     pub gen_pngs: bool,
+    // a single duplex ordered pdf of all of the sheets, which is what people actually print
+    pub gen_pdf: bool,
     pub cutlines_on: bool,
+    // marks in the margins lining up with each card's cutline, for cutting with a guillotine or a trimmer, with registration marks to check that the backs line up. They only go where there's room around the grid.
+    pub crop_marks: bool,
+    pub paper: PaperSize,
+    // kept clear around the edge of the page, for printers that can't print there
+    pub margin: f64,
+    // how big the cards are printed relative to their real size (bleed included). We've been printing them at two thirds so that 36 fit on a letter sheet.
+    pub card_scale: f64,
+    pub duplex: DuplexFlip,
+    // shifts the backs, for printers that don't line them up with the fronts. Print a sheet, hold it up to a light, measure how far the backs are off and put the opposite here.
+    pub back_offset: V2,
+    // see CardPrintConf::low_ink. Also leaves the sheets white.
+    pub low_ink: bool,
+}
+impl Default for PnpGen {
+    fn default() -> Self {
+        Self {
+            gen_svgs: true,
+            gen_pngs: false,
+            gen_pdf: true,
+            cutlines_on: true,
+            crop_marks: false,
+            paper: PaperSize::Letter,
+            margin: 0.0,
+            card_scale: 2.0 / 3.0,
+            duplex: DuplexFlip::LongEdge,
+            back_offset: V2::new(0.0, 0.0),
+            low_ink: false,
+        }
+    }
+}
+impl PnpGen {
+    pub fn card_span(&self, card_bounds: V2) -> V2 {
+        card_bounds * self.card_scale
+    }
+    // as many cards as fit within the margins
+    pub fn grid(&self, card_bounds: V2) -> (usize, usize) {
+        let printable = self.paper.dims() - both_dims(2.0 * self.margin);
+        let span = self.card_span(card_bounds);
+        // the epsilon is so that cards that fit exactly aren't lost to rounding
+        let fit = |room: f64, s: f64| ((room + 0.0001) / s).floor().max(0.0) as usize;
+        (fit(printable.x, span.x), fit(printable.y, span.y))
+    }
 }
 
-pub fn print_and_play_sheets<I>(assets: &Assets, cards: I, output_dir: &Path, cutlines_on: bool)
-where
-    I: Iterator<Item = (usize, Rc<Asset>, Rc<Asset>)> + Clone,
-{
+// design units are 1/79.375in (see DESIGN_DPI)
+const DESIGN_UNITS_PER_INCH: f64 = 79.375;
+const DESIGN_UNITS_PER_MM: f64 = DESIGN_UNITS_PER_INCH / 25.4;
+
+#[derive(Clone, Copy, Debug)]
+pub enum PaperSize {
+    Letter,
+    A4,
+    A3,
+}
+impl PaperSize {
+    // portrait
+    pub fn dims(self) -> V2 {
+        match self {
+            PaperSize::Letter => V2::new(8.5, 11.0) * DESIGN_UNITS_PER_INCH,
+            PaperSize::A4 => V2::new(210.0, 297.0) * DESIGN_UNITS_PER_MM,
+            PaperSize::A3 => V2::new(297.0, 420.0) * DESIGN_UNITS_PER_MM,
+        }
+    }
+}
+
+const CROP_MARK_LENGTH: f64 = DESIGN_UNITS_PER_INCH / 4.0;
+// between the grid and the start of the crop marks, so they don't get drawn over the bleed
+const CROP_MARK_GAP: f64 = DESIGN_UNITS_PER_INCH / 16.0;
+const REGISTRATION_MARK_RAD: f64 = DESIGN_UNITS_PER_INCH / 16.0;
+const PRINT_MARK_STYLE: &str = "fill:none;stroke:#000000;stroke-width:0.3";
+
+// grid is the area covered by cards, cuts_x and cuts_y are where the cutlines are
+fn crop_marks(grid: Rect, cuts_x: &[f64], cuts_y: &[f64], page_dims: V2, w: &mut dyn Write) {
+    let mut line = |from: V2, to: V2| {
+        write!(
+            w,
+            r##"<path d="M {},{} L {},{}" style="{PRINT_MARK_STYLE}" />"##,
+            from.x, from.y, to.x, to.y
+        )
+        .unwrap();
+    };
+    // from the start of the margin outwards, by at most CROP_MARK_LENGTH, stopping at the page edge. Returns None if there's no room
+    let mark = |start: f64, direction: f64, page_edge: f64| -> Option<(f64, f64)> {
+        let from = start + direction * CROP_MARK_GAP;
+        let room = (page_edge - from) * direction;
+        (room > 0.0).then(|| (from, from + direction * room.min(CROP_MARK_LENGTH)))
+    };
+    for x in cuts_x.iter().copied() {
+        if let Some((a, b)) = mark(grid.ul.y, -1.0, 0.0) {
+            line(V2::new(x, a), V2::new(x, b));
+        }
+        if let Some((a, b)) = mark(grid.br.y, 1.0, page_dims.y) {
+            line(V2::new(x, a), V2::new(x, b));
+        }
+    }
+    for y in cuts_y.iter().copied() {
+        if let Some((a, b)) = mark(grid.ul.x, -1.0, 0.0) {
+            line(V2::new(a, y), V2::new(b, y));
+        }
+        if let Some((a, b)) = mark(grid.br.x, 1.0, page_dims.x) {
+            line(V2::new(a, y), V2::new(b, y));
+        }
+    }
+    // registration marks in the middle of each margin that has room for one
+    let needs = CROP_MARK_GAP + 2.0 * REGISTRATION_MARK_RAD;
+    let center = grid.center();
+    let margins = [
+        (grid.ul.y, V2::new(center.x, grid.ul.y / 2.0)),
+        (page_dims.y - grid.br.y, V2::new(center.x, (grid.br.y + page_dims.y) / 2.0)),
+        (grid.ul.x, V2::new(grid.ul.x / 2.0, center.y)),
+        (page_dims.x - grid.br.x, V2::new((grid.br.x + page_dims.x) / 2.0, center.y)),
+    ];
+    for (room, at) in margins {
+        if room >= needs {
+            let r = REGISTRATION_MARK_RAD;
+            write!(
+                w,
+                r##"<circle cx="{}" cy="{}" r="{}" style="{PRINT_MARK_STYLE}" /><path d="M {},{} H {} M {},{} V {}" style="{PRINT_MARK_STYLE}" />"##,
+                at.x,
+                at.y,
+                r * 0.7,
+                at.x - r,
+                at.y,
+                at.x + r,
+                at.x,
+                at.y - r,
+                at.y + r
+            )
+            .unwrap();
+        }
+    }
+}
+
+// which edge of the sheet the printer turns it over on, which decides where each back has to go to land behind its front
+#[derive(Clone, Copy, Debug)]
+pub enum DuplexFlip {
+    // the usual, backs are mirrored left to right
+    LongEdge,
+    // backs are mirrored top to bottom
+    ShortEdge,
+}
+
+// (repeat, front, back)
+pub type PnpCard = (usize, Rc<Asset>, Rc<Asset>);
+
+// each group starts on a fresh sheet, so that a deck (or a level of one) can be printed on its own. Returns each group's sheets in duplex order, each front followed by its back.
+pub fn print_and_play_sheets(
+    assets: &Assets,
+    groups: &[(String, Vec<PnpCard>)],
+    output_dir: &Path,
+    conf: &PnpGen,
+) -> Vec<(String, Vec<PathBuf>)> {
     clear_or_create(output_dir);
-    let (cards_front, cards_back): (Vec<Rc<Asset>>, Vec<Rc<Asset>>) = cards
-        .flat_map(|(r, f, b)| iter::repeat_with(move || (f.clone(), b.clone())).take(r))
-        .unzip();
-    let card_count = cards_front.len();
-    let tx = 6;
-    let ty = 6;
-    let sheets_needed = card_count.div_ceil(tx * ty);
-    let page_dims = V2::new(674.688, 873.125);
-    let cs = assets.pnpmask.bounds;
-    let card_scale = (page_dims.x / tx as f64 / cs.x).min(page_dims.y / ty as f64 / cs.y);
-    let card_span = cs * card_scale;
-
-    let do_side = |cards: &Vec<Rc<Asset>>, is_front: bool| {
+    let mask = assets.pnp_mask(conf.low_ink);
+    let cs = mask.bounds;
+    let (tx, ty) = conf.grid(cs);
+    assert!(
+        tx > 0 && ty > 0,
+        "cards at scale {} don't fit on {:?} paper with a margin of {}",
+        conf.card_scale,
+        conf.paper,
+        conf.margin
+    );
+    let page_dims = conf.paper.dims();
+    let card_scale = conf.card_scale;
+    let card_span = conf.card_span(cs);
+    // the grid is centered, so that mirroring it for the backs lines it up with itself
+    let grid_ul = (page_dims - V2::new(tx as f64 * card_span.x, ty as f64 * card_span.y)) / 2.0;
+    let cutlines_on = conf.cutlines_on;
+    let crop_marks_on = conf.crop_marks;
+    let background = if conf.low_ink {
+        LOW_INK_BACKGROUND_COLOR
+    } else {
+        CARD_BACKGROUND_COLOR
+    };
+    // where the card in this cell goes, and where the lines along its left and top edges go
+    let cell_ul = |x: usize, y: usize, is_front: bool| -> V2 {
+        let front = grid_ul + V2::new(card_span.x * x as f64, card_span.y * y as f64);
+        if is_front {
+            front
+        } else {
+            let mirrored = match conf.duplex {
+                DuplexFlip::LongEdge => V2::new(page_dims.x - front.x - card_span.x, front.y),
+                DuplexFlip::ShortEdge => V2::new(front.x, page_dims.y - front.y - card_span.y),
+            };
+            mirrored + conf.back_offset
+        }
+    };
+
+    let do_side = |group: &str, cards: &Vec<Rc<Asset>>, is_front: bool| -> Vec<PathBuf> {
+        let sheets_needed = cards.len().div_ceil(tx * ty);
         let mut cards = cards.iter();
+        let mut pages = Vec::new();
         for sheeti in 0..sheets_needed {
             let side = if is_front { "[face]" } else { "[back]" };
             let file_name = if sheets_needed == 1 {
-                format!("sheet{side}.svg")
+                format!("{group}_sheet{side}.svg")
             } else {
-                format!("sheet{sheeti}{side}.svg")
+                format!("{group}_sheet{sheeti}{side}.svg")
             };
-            let mut w = File::create(output_dir.join(file_name)).unwrap();
+            let page = output_dir.join(file_name);
+            let mut w = File::create(&page).unwrap();
+            pages.push(page);
 
             //displayings take immutable fns so we can't do this inline
             let mut inner_first = Vec::new();
@@ -2619,19 +3591,10 @@ where
             'outer: for y in 0..ty {
                 for x in 0..tx {
                     if let Some(cn) = cards.next() {
-                        // let lx = if is_front { x } else { tx - 1 - x };
-                        let mx = card_span.x * x as f64;
-                        let ul = V2::new(
-                            if is_front {
-                                mx
-                            } else {
-                                page_dims.x - mx - card_span.x
-                            },
-                            card_span.y * y as f64,
-                        );
+                        let ul = cell_ul(x, y, is_front);
                         //render to different buffers to make sure the blur of the cards doesn't overlap any of the masks
                         cn.by_ul(ul, card_scale, &mut inner_first);
-                        assets.pnpmask.by_ul(ul, card_scale, &mut inner_second);
+                        mask.by_ul(ul, card_scale, &mut inner_second);
                     } else {
                         break 'outer;
                     }; //checked at function start
@@ -2640,18 +3603,17 @@ where
             
             svg_outer(
                 page_dims,
-                CARD_BACKGROUND_COLOR,
+                background,
                 &Displaying(|w| {
                     w.write_all(&inner_first).unwrap();
                     w.write_all(&inner_second).unwrap();
                     if cutlines_on {
+                        // the grid's upper left corner, wherever it ended up on this side
+                        let ul = cell_ul(0, 0, is_front).inf(&cell_ul(tx - 1, ty - 1, is_front));
                         //vertical
                         let vertical_line_length = page_dims.y + 2.0;
                         for ci in 0..(tx+1) {
-                            let mut offset = ci as f64 * card_span.x;
-                            if !is_front {
-                                offset = page_dims.x - offset;
-                            }
+                            let offset = ul.x + ci as f64 * card_span.x;
                             write!(w,
                             "<path
                                 style=\"opacity:0.5;fill:none;stroke-width:1.9;stroke-linecap:round;stroke-linejoin:round;fill-opacity:1;stroke:#ebebeb;stroke-opacity:1;stroke-dasharray:none\"
@@ -2661,7 +3623,7 @@ where
                         //horizontal
                         let horizontal_line_length = page_dims.x + 2.0;
                         for ci in 0..(ty+1) {
-                            let offset = ci as f64 * card_span.y;
+                            let offset = ul.y + ci as f64 * card_span.y;
                             write!(w,
                             "<path
                                 style=\"opacity:0.5;fill:none;stroke-width:1.9;stroke-linecap:round;stroke-linejoin:round;fill-opacity:1;stroke:#ebebeb;stroke-opacity:1;stroke-dasharray:none\"
@@ -2669,11 +3631,201 @@ where
                                 id=\"path1\" />").unwrap();
                         }
                     }
+                    if crop_marks_on {
+                        let ul = cell_ul(0, 0, is_front).inf(&cell_ul(tx - 1, ty - 1, is_front));
+                        let grid = Rect {
+                            ul,
+                            br: ul + V2::new(tx as f64 * card_span.x, ty as f64 * card_span.y),
+                        };
+                        let inset = both_dims(BLEED_INCHES * DESIGN_UNITS_PER_INCH) * card_scale;
+                        let cuts = |from: f64, n: usize, span: f64, inset: f64| -> Vec<f64> {
+                            (0..n)
+                                .flat_map(|i| {
+                                    let o = from + i as f64 * span;
+                                    [o + inset, o + span - inset]
+                                })
+                                .collect()
+                        };
+                        crop_marks(
+                            grid,
+                            &cuts(ul.x, tx, card_span.x, inset.x),
+                            &cuts(ul.y, ty, card_span.y, inset.y),
+                            page_dims,
+                            w,
+                        );
+                    }
                 }),
                 &mut w,
             );
         }
+        pages
     };
-    do_side(&cards_front, true);
-    do_side(&cards_back, false);
+    groups
+        .iter()
+        .map(|(group, cards)| {
+            let (cards_front, cards_back): (Vec<Rc<Asset>>, Vec<Rc<Asset>>) = cards
+                .iter()
+                .flat_map(|(r, f, b)| iter::repeat_with(move || (f.clone(), b.clone())).take(*r))
+                .unzip();
+            let fronts = do_side(group, &cards_front, true);
+            let backs = do_side(group, &cards_back, false);
+            let pages = fronts.into_iter().zip(backs).flat_map(|(f, b)| [f, b]).collect();
+            (group.clone(), pages)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(svg: &str) -> Result<Asset, String> {
+        asset_from_svg(&elementtree::Element::from_reader(svg.as_bytes()).unwrap(), "test", None)
+    }
+
+    #[test]
+    fn reads_lengths_in_every_unit() {
+        for (v, mm) in [
+            ("10mm", 10.0),
+            ("1cm", 10.0),
+            ("1in", 25.4),
+            ("72pt", 25.4),
+            ("6pc", 25.4),
+            ("96px", 25.4),
+            ("96", 25.4),
+            ("4Q", 1.0),
+        ] {
+            assert!((parse_length(v).unwrap() - mm).abs() < 1e-9, "{v}");
+        }
+        assert!(parse_length("50%").is_err());
+        assert!(parse_length("wide").is_err());
+    }
+
+    #[test]
+    fn fills_in_missing_dimensions_from_the_view_box() {
+        let a = asset(r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" viewBox="0 0 10 5"/>"#).unwrap();
+        assert_eq!(a.bounds, V2::new(20.0, 10.0));
+        let a = asset(r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 96 48"/>"#).unwrap();
+        assert!((a.bounds - V2::new(25.4, 12.7)).norm() < 1e-9);
+        let e = asset(r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm"/>"#).err().unwrap();
+        assert!(e.contains("no height"), "{e}");
+    }
+
+    #[test]
+    fn scales_content_into_the_view_box() {
+        let a = asset(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="10mm" viewBox="0 0 5 5"><path d="M 0,0"/></svg>"#,
+        )
+        .unwrap();
+        let mut out = Vec::new();
+        (a.render)(V2::zeros(), 1.0, 0.0, &mut out);
+        // meet, so the square viewBox is scaled to the height and centered horizontally
+        assert!(String::from_utf8(out).unwrap().contains("matrix(2,0,0,2,5,0)"));
+        let m = view_box_transform(&parse_view_box("0 0 10 10").unwrap(), V2::new(20.0, 10.0), Some("none")).unwrap();
+        assert_eq!(m, [2.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn reads_anchors_from_the_svg() {
+        let a = asset(r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 10 10" data-anchor="2 9"/>"#).unwrap();
+        assert_eq!(a.anchor, V2::new(4.0, 18.0));
+        let a = asset(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:inkscape="http://www.inkscape.org/namespaces/inkscape" width="10mm" height="10mm" viewBox="0 0 10 10"><g transform="translate(1,2)"><circle inkscape:label="anchor" cx="3" cy="4" r="0.5"/><path d="M 0,0"/></g></svg>"#,
+        )
+        .unwrap();
+        assert_eq!(a.anchor, V2::new(4.0, 6.0));
+        let mut out = Vec::new();
+        (a.render)(V2::zeros(), 1.0, 0.0, &mut out);
+        // the marker isn't drawn
+        assert!(!String::from_utf8(out).unwrap().contains("circle"));
+        let a = asset(r#"<svg xmlns="http://www.w3.org/2000/svg" width="10mm" height="4mm"/>"#).unwrap();
+        assert_eq!(a.anchor, V2::new(5.0, 2.0));
+    }
+
+    #[test]
+    fn overlays_replace_assets_by_name() {
+        let base = std::env::temp_dir().join(format!("cardgen_assets_{}", std::process::id()));
+        let overlay = base.join("overlay");
+        std::fs::create_dir_all(&overlay).unwrap();
+        let square = |mm: f64| format!(r#"<svg xmlns="http://www.w3.org/2000/svg" width="{mm}mm" height="{mm}mm"/>"#);
+        std::fs::write(base.join("a.svg"), square(1.0)).unwrap();
+        std::fs::write(base.join("b.svg"), square(2.0)).unwrap();
+        std::fs::write(overlay.join("b.svg"), square(3.0)).unwrap();
+        let registry = AssetRegistry::scan(&base, &[overlay]).unwrap();
+        assert_eq!(registry.names(), vec!["a", "b"]);
+        assert_eq!(registry.get("a").unwrap().bounds, V2::new(1.0, 1.0));
+        assert_eq!(registry.get("b").unwrap().bounds, V2::new(3.0, 3.0));
+        assert!(registry.get("c").err().unwrap().contains("\"c\""));
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn card_formats_derive_the_layout() {
+        // the mini layout is the one everything was drawn for
+        let mini = MINI_CARDS;
+        assert!((mini.dimensions() - V2::new(158.75, 218.28125)).norm() < 1e-9);
+        assert!((mini.cutline_inset().x - 9.921875).abs() < 1e-9);
+        assert_eq!(mini.print_scale(), 1.0);
+        // other formats are laid out at the same width, and printed at their own size
+        let poker = POKER_CARDS;
+        assert_eq!(poker.dimensions().x, mini.dimensions().x);
+        assert!((poker.print_dimensions() - V2::new(2.75, 3.75) * DESIGN_UNITS_PER_INCH).norm() < 1e-9);
+        set_card_format(poker);
+        let cut = cutline_bounds();
+        assert!((end_graphic_center().y + graphic_rad() - cut.br.y).abs() < 1e-9);
+        assert!(means_graphic_usual_bounds().br.y < cut.br.y);
+        set_card_format(MINI_CARDS);
+        assert!((end_graphic_center() - V2::new(79.375, 138.90625)).norm() < 1e-9);
+    }
+
+    #[test]
+    fn namespaces_the_ids_a_fragment_defines() {
+        let fragment = r##"<g id="a" data-id="a"><clipPath id='b'/><path style="clip-path:url('#b');fill:url(#a)" mask="url(&quot;#c&quot;)"/><use xlink:href="#a"/><use href="#outside"/><path fill="url(#outside)"/></g>"##;
+        assert_eq!(
+            namespace_ids(fragment, "p-"),
+            r##"<g id="p-a" data-id="a"><clipPath id='p-b'/><path style="clip-path:url('#p-b');fill:url(#p-a)" mask="url(&quot;#c&quot;)"/><use xlink:href="#p-a"/><use href="#outside"/><path fill="url(#outside)"/></g>"##
+        );
+        assert_eq!(
+            namespace_ids(r##"<path d="M 0,0" id="x"/><use href="#x"/>"##, "q-"),
+            r##"<path d="M 0,0" id="q-x"/><use href="#q-x"/>"##
+        );
+    }
+
+    #[test]
+    fn exporters_put_the_print_conf_back() {
+        let users = CardPrintConf { low_ink: true, ..CardPrintConf::default() };
+        set_card_print_conf(users);
+        let during = with_card_print_conf(CardPrintConf::default(), card_print_conf);
+        assert!(!during.low_ink);
+        assert!(card_print_conf().low_ink);
+        set_card_print_conf(CardPrintConf::default());
+    }
+
+    #[test]
+    fn cards_are_structurally_sound() {
+        let assets = Rc::new(Assets::load(Path::new("assets")));
+        // with the bleed and the overlay on, so that the extensions' references are checked too
+        let conf = CardPrintConf {
+            extra_bleed: BLEED_INCHES * DESIGN_UNITS_PER_INCH,
+            safe_zone_overlay: true,
+            ..CardPrintConf::default()
+        };
+        with_card_print_conf(conf, || {
+            let gens = crate::generation::end_specs(&assets)
+                .into_iter()
+                .chain(crate::generation::means_specs(&assets))
+                .chain(crate::generation::land_specs_card(&assets, &[1, 1, 1, 1]));
+            for gen in gens {
+                let spec = gen.generator.get(0);
+                for (side, draw) in [("front", &spec.generate_front), ("back", &spec.generate_back)] {
+                    let mut svg = Vec::new();
+                    draw(&mut svg);
+                    let tree = Element::parse(&String::from_utf8(svg).unwrap())
+                        .unwrap_or_else(|e| panic!("the {side} of {} isn't xml: {e}", spec.name));
+                    let problems = tree.check();
+                    assert!(problems.is_empty(), "the {side} of {}: {problems:?}", spec.name);
+                }
+            }
+        });
+    }
 }
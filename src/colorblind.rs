@@ -0,0 +1,197 @@
+// for players who can't tell the elements apart by color. When it's on, every element graphic gets a little badge with a glyph that's different for each element, and the theme's colors are checked for pairs that look the same under simulated color blindness.
+
+use std::{cell::Cell, fmt::Display, io::Write};
+
+use super::*;
+
+thread_local! {
+    static COLORBLIND_MARKS: Cell<bool> = const { Cell::new(false) };
+}
+pub fn set_colorblind_marks(on: bool) {
+    COLORBLIND_MARKS.with(|c| c.set(on));
+}
+pub fn colorblind_marks() -> bool {
+    COLORBLIND_MARKS.with(|c| c.get())
+}
+
+const MARK_COLOR: &str = "404040";
+const MARK_BACKGROUND_COLOR: &str = "ffffff";
+
+// draws the badge over the lower right of an element graphic centered at center with radius rad, if colorblind marks are on. The badge stays upright even if the graphic's rotated, rotation is in degrees.
+pub fn element_mark(e: ElementTag, center: V2, rad: f64, rotation: f64, to: &mut dyn Write) {
+    if !colorblind_marks() {
+        return;
+    }
+    let at = center + rotate(from_angle(rotation.to_radians()), V2::new(0.6, 0.6) * rad);
+    let r = rad * 0.34;
+    let stroke = rad * 0.05;
+    write!(
+        to,
+        r##"<g transform="translate({},{}) scale({r})"><circle r="1" style="fill:#{MARK_BACKGROUND_COLOR};stroke:#{MARK_COLOR};stroke-width:{}" />"##,
+        at.x,
+        at.y,
+        stroke / r
+    )
+    .unwrap();
+    let filled = format!("fill:#{MARK_COLOR};stroke:none");
+    let lined = format!("fill:none;stroke:#{MARK_COLOR};stroke-width:0.16;stroke-linecap:round;stroke-linejoin:round");
+    // drawn within the unit circle
+    let glyph = match e {
+        FIELD => format!(r##"<circle r="0.3" style="{filled}" />"##),
+        FOREST => format!(r##"<path d="M 0,-0.55 L 0.5,0.4 H -0.5 Z" style="{filled}" />"##),
+        MOUNTAIN => format!(r##"<path d="M -0.5,0.3 L 0,-0.35 L 0.5,0.3" style="{lined}" />"##),
+        VOLCANO => format!(
+            r##"<path d="M 0,-0.5 V 0.5 M -0.43,-0.25 L 0.43,0.25 M -0.43,0.25 L 0.43,-0.25" style="{lined}" />"##
+        ),
+        LAKE => format!(
+            r##"<path d="M -0.5,-0.15 Q -0.25,-0.35 0,-0.15 T 0.5,-0.15 M -0.5,0.25 Q -0.25,0.05 0,0.25 T 0.5,0.25" style="{lined}" />"##
+        ),
+        ICE => format!(r##"<path d="M 0,-0.5 L 0.45,0 L 0,0.5 L -0.45,0 Z" style="{lined}" />"##),
+        TOMB => format!(r##"<path d="M 0,-0.5 V 0.5 M -0.5,0 H 0.5" style="{lined}" />"##),
+        VOID => format!(r##"<circle r="0.4" style="{lined}" />"##),
+        _ => panic!("no such element as {e}"),
+    };
+    write!(to, "{glyph}</g>").unwrap();
+}
+
+// for element assets loaded from files
+pub fn with_element_mark(a: Asset, e: ElementTag) -> Asset {
+    let render = a.render.clone();
+    let bounds = a.bounds;
+    Asset {
+        render: Rc::new(move |ul: V2, scale: f64, rotation: f64, to: &mut dyn Write| {
+            render(ul, scale, rotation, to);
+            let center = ul + rotate(from_angle(rotation.to_radians()), bounds * scale / 2.0);
+            element_mark(e, center, bounds.min() * scale / 2.0, rotation, to);
+        }),
+        ..a
+    }
+}
+
+// colors closer than this (CIE76 delta E) are hard to tell apart
+const MIN_DELTA_E: f64 = 10.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Vision {
+    Protanopia,
+    Deuteranopia,
+}
+impl Vision {
+    // machado, oliveira and fernandes 2009, at full severity. Applies to linear rgb.
+    fn matrix(self) -> [[f64; 3]; 3] {
+        match self {
+            Vision::Protanopia => [
+                [0.152286, 1.052583, -0.204868],
+                [0.114503, 0.786281, 0.099216],
+                [-0.003882, -0.048116, 1.051998],
+            ],
+            Vision::Deuteranopia => [
+                [0.367322, 0.860646, -0.227968],
+                [0.280085, 0.672501, 0.047413],
+                [-0.011820, 0.042940, 0.968881],
+            ],
+        }
+    }
+}
+
+pub fn linear_channel(c: u8) -> f64 {
+    let c = c as f64 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_rgb(hex: &str) -> [f64; 3] {
+    let channel = |i: usize| linear_channel(u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).unwrap());
+    [channel(0), channel(1), channel(2)]
+}
+
+// how the color looks to someone with that vision, in CIELAB
+fn lab_as_seen(hex: &str, vision: Vision) -> [f64; 3] {
+    let c = linear_rgb(hex);
+    let m = vision.matrix();
+    lab(m.map(|row| (row[0] * c[0] + row[1] * c[1] + row[2] * c[2]).clamp(0.0, 1.0)))
+}
+
+// CIELAB from linear rgb, with a d65 white point
+pub fn lab([r, g, b]: [f64; 3]) -> [f64; 3] {
+    let x = (0.4124 * r + 0.3576 * g + 0.1805 * b) / 0.95047;
+    let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    let z = (0.0193 * r + 0.1192 * g + 0.9505 * b) / 1.08883;
+    let f = |t: f64| {
+        if t > 0.008856 {
+            t.cbrt()
+        } else {
+            7.787 * t + 16.0 / 116.0
+        }
+    };
+    let (fx, fy, fz) = (f(x), f(y), f(z));
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+pub fn delta_e(a: [f64; 3], b: [f64; 3]) -> f64 {
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+}
+
+pub struct ContrastProblem {
+    // "tile" or "graphic"
+    pub palette: &'static str,
+    pub a: ElementTag,
+    pub b: ElementTag,
+    pub vision: Vision,
+    pub delta_e: f64,
+}
+impl Display for ContrastProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let colors = |e: ElementTag| match self.palette {
+            "tile" => element_color_back(e),
+            _ => element_color_front(e),
+        };
+        write!(
+            f,
+            "the {} colors of {} (#{}) and {} (#{}) are only {:.1} apart with {:?}",
+            self.palette,
+            ELEMENT_NAMES[self.a],
+            colors(self.a),
+            ELEMENT_NAMES[self.b],
+            colors(self.b),
+            self.delta_e,
+            self.vision
+        )
+    }
+}
+
+// every pair of elements whose tile colors (or whose graphics' colors) are too close together to someone with protanopia or deuteranopia
+pub fn colorblind_contrast_problems() -> Vec<ContrastProblem> {
+    let mut r = Vec::new();
+    let palettes: [(&str, [&str; 8]); 2] = [
+        ("tile", std::array::from_fn(element_color_back)),
+        ("graphic", std::array::from_fn(element_color_front)),
+    ];
+    for (palette, colors) in palettes {
+        for vision in [Vision::Protanopia, Vision::Deuteranopia] {
+            for (a, ca) in colors.iter().enumerate() {
+                for (b, cb) in colors.iter().enumerate().skip(a + 1) {
+                    let d = delta_e(lab_as_seen(ca, vision), lab_as_seen(cb, vision));
+                    if d < MIN_DELTA_E {
+                        r.push(ContrastProblem { palette, a, b, vision, delta_e: d });
+                    }
+                }
+            }
+        }
+    }
+    r
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn element_colors_stay_distinguishable() {
+        let problems: Vec<String> = colorblind_contrast_problems().iter().map(|p| p.to_string()).collect();
+        assert!(problems.is_empty(), "{}", problems.join("\n"));
+    }
+}
@@ -0,0 +1,126 @@
+// big overview sheets with every card on them, labelled, so that a whole deck can be reviewed at a glance instead of file by file
+
+use std::{fs::File, io::Write, path::PathBuf, rc::Rc};
+
+use resvg::usvg::fontdb::Database;
+
+use super::*;
+
+pub struct ContactSheetConf {
+    pub output_dir: PathBuf,
+    // puts each card's back to the right of its front
+    pub include_backs: bool,
+    // cards per row. Each group starts a new row.
+    pub columns: usize,
+    pub gen_pngs: bool,
+    // relative to print resolution, full size pngs of a whole deck are enormous
+    pub png_scale: f64,
+}
+impl Default for ContactSheetConf {
+    fn default() -> Self {
+        Self {
+            output_dir: PathBuf::from("contact_sheets"),
+            include_backs: false,
+            columns: 8,
+            gen_pngs: true,
+            png_scale: 0.25,
+        }
+    }
+}
+
+const GAP: f64 = 12.0;
+const LABEL_HEIGHT: f64 = 16.0;
+const LABEL_FONT_SIZE: f64 = 9.0;
+const HEADER_HEIGHT: f64 = 26.0;
+const HEADER_FONT_SIZE: f64 = 14.0;
+const LABEL_STYLE: &str = "font-family:Rubik;text-anchor:middle;fill:#404040";
+
+// groups are usually one per CardGen. Writes {name}.svg (and .png) to the conf's output dir, which the caller is expected to have cleared.
+pub fn contact_sheet(
+    name: &str,
+    groups: &[(String, Vec<CardSpec>)],
+    conf: &ContactSheetConf,
+    fonts: &Database,
+) {
+    with_card_print_conf(CardPrintConf::default(), || {
+        let card = card_format().print_dimensions();
+        let sides = if conf.include_backs { 2.0 } else { 1.0 };
+        let cell = V2::new(
+            card.x * sides + GAP * (sides - 1.0) / 2.0 + GAP,
+            card.y + LABEL_HEIGHT + GAP,
+        );
+        let width = GAP + cell.x * conf.columns as f64;
+        let render = |spec: &CardSpec, generate: &dyn Fn(&mut dyn Write)| {
+            let mut data = Vec::new();
+            generate(&mut data);
+            Rc::new(asset_from_svg_data(&data, &spec.name, None))
+        };
+
+        let mut body = Vec::new();
+        let mut y = GAP;
+        for (label, specs) in groups.iter().filter(|(_, s)| !s.is_empty()) {
+            write!(
+                body,
+                r##"<text x="{}" y="{}" style="{LABEL_STYLE};font-size:{HEADER_FONT_SIZE}px;text-anchor:start">{} ({})</text>"##,
+                GAP,
+                y + HEADER_FONT_SIZE,
+                escaped(label, false),
+                specs.len()
+            )
+            .unwrap();
+            y += HEADER_HEIGHT;
+            for row in specs.chunks(conf.columns) {
+                for (i, spec) in row.iter().enumerate() {
+                    let ul = V2::new(GAP + cell.x * i as f64, y);
+                    render(spec, &*spec.generate_front).by_ul(ul, 1.0, &mut body);
+                    if conf.include_backs {
+                        render(spec, &*spec.generate_back)
+                            .by_ul(ul + V2::new(card.x + GAP / 2.0, 0.0), 1.0, &mut body);
+                    }
+                    for o in take_text_overflows() {
+                        println!(
+                            "warning, the back of {} still doesn't fit at {}px: {:?}",
+                            spec.name, o.font_size, o.text
+                        );
+                    }
+                    let repeat = if spec.repeat > 1 {
+                        format!(" ×{}", spec.repeat)
+                    } else {
+                        String::new()
+                    };
+                    write!(
+                        body,
+                        r##"<text x="{}" y="{}" style="{LABEL_STYLE};font-size:{LABEL_FONT_SIZE}px">{}{repeat}</text>"##,
+                        ul.x + (cell.x - GAP) / 2.0,
+                        y + card.y + LABEL_HEIGHT - (LABEL_HEIGHT - LABEL_FONT_SIZE) / 2.0,
+                        escaped(&spec.name, false)
+                    )
+                    .unwrap();
+                }
+                y += cell.y;
+            }
+        }
+
+        let span = V2::new(width, y);
+        let svg_path = localized(&conf.output_dir).join(format!("{name}.svg"));
+        let mut svg = Vec::new();
+        svg_outer(
+            span,
+            CARD_BACKGROUND_COLOR,
+            &Displaying(|w| w.write_all(&body).unwrap()),
+            &mut svg,
+        );
+        File::create(&svg_path).unwrap().write_all(&svg).unwrap();
+        if conf.gen_pngs {
+            // css pixels per design unit, see DESIGN_DPI
+            let px = 96.0 / 25.4 * conf.png_scale;
+            svg_data_to_png(
+                &svg,
+                name,
+                &svg_path.with_extension("png"),
+                fonts,
+                Some(((span.x * px).round() as u32, (span.y * px).round() as u32)),
+            );
+        }
+    })
+}
@@ -0,0 +1,181 @@
+// a single html file cataloguing every card, with filters, for reference while designing and as the card appendix on the site. The card images are embedded, so the file can be put anywhere.
+
+use std::{fmt::Write as _, path::PathBuf};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use super::*;
+
+pub struct GalleryConf {
+    pub output: PathBuf,
+}
+impl Default for GalleryConf {
+    fn default() -> Self {
+        Self {
+            // next to manual.md
+            output: PathBuf::from("cards.html"),
+        }
+    }
+}
+
+pub fn write_gallery(specs: &[CardSpec], conf: &GalleryConf) {
+    with_card_print_conf(CardPrintConf::default(), || {
+        let mut specs: Vec<&CardSpec> = specs.iter().collect();
+        specs.sort_by(|a, b| (a.deck, a.level, a.clown, &a.name).cmp(&(b.deck, b.level, b.clown, &b.name)));
+
+        let image = |generate: &dyn Fn(&mut dyn std::io::Write)| {
+            let mut data = Vec::new();
+            generate(&mut data);
+            format!("data:image/svg+xml;base64,{}", STANDARD.encode(data))
+        };
+        let mut cards = String::new();
+        for spec in specs.iter() {
+            let front = image(&*spec.generate_front);
+            let back = image(&*spec.generate_back);
+            // eg "Kill:field,forest;Move:lake", read by the filter script
+            let props = spec
+                .properties
+                .iter()
+                .map(|(k, es)| {
+                    let es: Vec<&str> = es.iter().map(|e| ELEMENT_NAMES[*e]).collect();
+                    format!("{k:?}:{}", es.join(","))
+                })
+                .collect::<Vec<_>>()
+                .join(";");
+            let props_listed = spec
+                .properties
+                .iter()
+                .map(|(k, es)| {
+                    let es: Vec<&str> = es.iter().map(|e| ELEMENT_NAMES[*e]).collect();
+                    format!("<li>{k:?}: {}</li>", es.join(", "))
+                })
+                .collect::<String>();
+            let clown = if spec.clown { "<li>clown</li>" } else { "" };
+            let level = if spec.deck == Deck::Land {
+                String::new()
+            } else {
+                format!("<li>level {}</li>", spec.level)
+            };
+            write!(
+                cards,
+                r#"<div class="card" data-deck="{deck}" data-level="{}" data-clown="{}" data-props="{props}">
+    <div class="sides"><img src="{front}" alt="front of {name}"><img src="{back}" alt="back of {name}"></div>
+    <h3>{name}</h3>
+    <p class="description">{}</p>
+    <ul>{level}{clown}<li>×{}</li>{props_listed}</ul>
+    </div>
+    "#,
+                spec.level,
+                spec.clown,
+                description_html(&spec.description),
+                spec.repeat,
+                deck = spec.deck.name(),
+                name = escaped(&spec.name, true),
+            )
+            .unwrap();
+        }
+
+        let options = |values: &[&str]| -> String {
+            values
+                .iter()
+                .map(|v| format!(r#"<option value="{v}">{v}</option>"#))
+                .collect()
+        };
+        let decks: Vec<&str> = [Deck::Ends, Deck::Means, Deck::Events, Deck::Land]
+            .iter()
+            .map(|d| d.name())
+            .collect();
+        let mut levels: Vec<String> = specs.iter().map(|s| s.level.to_string()).collect();
+        levels.sort();
+        levels.dedup();
+        let levels: Vec<&str> = levels.iter().map(|l| l.as_str()).collect();
+        let html = format!(
+            r#"<!DOCTYPE html>
+    <html>
+    <head>
+    <meta charset="utf-8">
+    <title>P1 cards</title>
+    <style>
+    body {{ font-family: Rubik, sans-serif; background: #f1f2f2; color: #404040; margin: 2em; }}
+    #filters {{ position: sticky; top: 0; background: #f1f2f2; padding: 0.5em 0; display: flex; gap: 1em; flex-wrap: wrap; }}
+    #cards {{ display: flex; flex-wrap: wrap; gap: 1.5em; }}
+    .card {{ width: 330px; }}
+    .card.hidden {{ display: none; }}
+    .sides {{ display: flex; gap: 6px; }}
+    .sides img {{ width: 162px; }}
+    .card h3 {{ margin: 0.3em 0 0.2em 0; font-size: 1em; }}
+    .card ul {{ margin: 0; padding-left: 1.2em; font-size: 0.85em; }}
+    .description {{ margin: 0.2em 0; font-size: 0.9em; }}
+    .icon {{ font-style: italic; }}
+    </style>
+    </head>
+    <body>
+    <h1>P1 cards</h1>
+    <div id="filters">
+    <label>deck <select id="deck"><option value="">any</option>{}</select></label>
+    <label>element <select id="element"><option value="">any</option>{}</select></label>
+    <label>kind <select id="kind"><option value="">any</option>{}</select></label>
+    <label>level <select id="level"><option value="">any</option>{}</select></label>
+    <label>clown <select id="clown"><option value="">either</option><option value="true">clown</option><option value="false">not clown</option></select></label>
+    <span id="count"></span>
+    </div>
+    <div id="cards">
+    {cards}</div>
+    <script>
+    const ids = ["deck", "element", "kind", "level", "clown"];
+    function filter() {{
+      const f = Object.fromEntries(ids.map(id => [id, document.getElementById(id).value]));
+      let shown = 0;
+      for (const card of document.querySelectorAll(".card")) {{
+        const props = card.dataset.props ? card.dataset.props.split(";").map(p => {{
+          const [kind, elements] = p.split(":");
+          return {{ kind, elements: elements ? elements.split(",") : [] }};
+        }}) : [];
+        // the element and kind have to be on the same property, "kills field" rather than "kills something, and mentions field"
+        const propsMatch = (!f.element && !f.kind) || props.some(p =>
+          (!f.kind || p.kind == f.kind) && (!f.element || p.elements.includes(f.element)));
+        const show = propsMatch
+          && (!f.deck || card.dataset.deck == f.deck)
+          && (!f.level || card.dataset.level == f.level)
+          && (!f.clown || card.dataset.clown == f.clown);
+        card.classList.toggle("hidden", !show);
+        if (show) {{ shown += 1; }}
+      }}
+      document.getElementById("count").textContent = shown + " cards";
+    }}
+    for (const id of ids) {{ document.getElementById(id).addEventListener("change", filter); }}
+    filter();
+    </script>
+    </body>
+    </html>
+    "#,
+            options(&decks),
+            options(&ELEMENT_NAMES),
+            options(&["Kill", "Move", "Change", "Preference"]),
+            options(&levels),
+        );
+        std::fs::write(localized(&conf.output), html).unwrap();
+    })
+}
+
+// {icon} markup (see Assets::icon) is shown as the icon's name
+fn description_html(description: &str) -> String {
+    let mut r = String::new();
+    let mut rest = description;
+    while let Some(open) = rest.find('{') {
+        r.push_str(&escaped(&rest[..open], true));
+        match rest[open..].find('}') {
+            Some(close) => {
+                let name = &rest[open + 1..open + close];
+                write!(r, r#"<span class="icon">{}</span>"#, escaped(name, true)).unwrap();
+                rest = &rest[open + close + 1..];
+            }
+            None => {
+                rest = &rest[open..];
+                break;
+            }
+        }
+    }
+    r.push_str(&escaped(rest, true));
+    r.replace('\n', "<br>")
+}
@@ -1,9 +1,9 @@
-use std::{cell::RefCell, f64::consts::TAU, rc::Rc};
+use std::{f64::consts::TAU, rc::Rc};
 
 use noisy_float::prelude::*;
 
 use mako_infinite_shuffle::{IndexVec, Indexing, Once};
-use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand::SeedableRng;
 
 use super::*;
 
@@ -24,15 +24,16 @@ pub fn end_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                         &all_assets,
                         format!("1_{}", ELEMENT_NAMES[e]),
                         Rc::new(Displaying(move |w| {
-                            (ELEMENT_G[e])(END_GRAPHIC_CENTER, 1.0, w)
+                            (ELEMENT_G[e])(end_graphic_center(), 1.0, w)
                         })),
                         scores.clone(),
                         1,
-                        format!("{} point for every {}", &scores, ELEMENT_NAMES_SINGULAR[e]),
+                        tr!("end-each", "{ $scores } point for every { $element-singular }", scores = &scores, element = El(e)),
                         vec![e],
                         1,
                         false,
                     )
+                    .with_desire(Desire::Each(e, 1.0))
                 }
             }),
         ),
@@ -74,14 +75,17 @@ pub fn end_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                         })),
                         "2".to_string(),
                         1,
-                        format!(
-                            "2 points for every adjacent pairing of {} and {}",
-                            ELEMENT_NAMES[e1], ELEMENT_NAMES[e2]
+                        tr!(
+                            "end-adjacent",
+                            "2 points for every adjacent pairing of { $a } and { $b }",
+                            a = El(e1),
+                            b = El(e2)
                         ),
                         vec![e1, e2],
                         0,
                         false,
                     )
+                    .with_desire(Desire::AdjacentPairs(e1, e2, 2.0))
                 }
             }),
         ),
@@ -118,14 +122,17 @@ pub fn end_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                     })),
                     scores.clone(),
                     1,
-                    format!(
-                        "{} points if there's exactly one {ename} at the end",
-                        &scores
+                    tr!(
+                        "end-just-1",
+                        "{ $scores } points if there's exactly one { $element } at the end",
+                        scores = &scores,
+                        element = El(e)
                     ),
                     vec![e],
                     0,
                     false,
                 )
+                .with_desire(Desire::Exactly(e, 1, 14.0))
             }
         })),
     });
@@ -152,13 +159,18 @@ pub fn end_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                     })),
                     scores.clone(),
                     1,
-                    format!(
-                        "1 point for each land included in a chain of {aname}, {bname}, {cname} (multiple chains may overlap, but don't count any land more than once)",
+                    tr!(
+                        "end-chain",
+                        "1 point for each land included in a chain of { $a }, { $b }, { $c } (multiple chains may overlap, but don't count any land more than once)",
+                        a = El(a),
+                        b = El(b),
+                        c = El(c)
                     ),
                     vec![a,b,c],
                     2,
                     false,
                 )
+                .with_desire(Desire::Chain(a, b, c))
             }
         })),
     });
@@ -170,7 +182,6 @@ pub fn end_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
             let all_assets = all_assets.clone();
             move |e| {
                 let scores = "13".to_string();
-                let ename_plural = ELEMENT_NAMES_PLURAL[e];
                 let ename = ELEMENT_NAMES[e];
                 CardSpec::end_card_with_back_blurred_message(
                     &all_assets,
@@ -203,14 +214,17 @@ pub fn end_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                     })),
                     scores.clone(),
                     1,
-                    format!(
-                        "{} points as long as there are exactly 2 {ename_plural} at the end",
-                        &scores
+                    tr!(
+                        "end-just-2",
+                        "{ $scores } points as long as there are exactly 2 { $element-plural } at the end",
+                        scores = &scores,
+                        element = El(e)
                     ),
                     vec![e],
                     0,
                     false,
                 )
+                .with_desire(Desire::Exactly(e, 2, 13.0))
             }
         })),
     });
@@ -230,7 +244,7 @@ pub fn end_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                 move |(e1, e2, e3)| {
                     let tilt = -TAU / 24.0;
                     let arc = TAU / 3.0;
-                    let r = GRAPHIC_RAD * 0.48;
+                    let r = graphic_rad() * 0.48;
                     let scale = 0.5;
 
                     let scores = "4".to_string();
@@ -246,17 +260,17 @@ pub fn end_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                                 w,
                                 "{}{}{}",
                                 &Displaying(|w: &mut dyn Write| ELEMENT_G[e1](
-                                    END_GRAPHIC_CENTER + from_angle_mag(tilt, r),
+                                    end_graphic_center() + from_angle_mag(tilt, r),
                                     scale,
                                     w
                                 )),
                                 &Displaying(|w: &mut dyn Write| ELEMENT_G[e2](
-                                    END_GRAPHIC_CENTER + from_angle_mag(tilt + arc, r),
+                                    end_graphic_center() + from_angle_mag(tilt + arc, r),
                                     scale,
                                     w
                                 )),
                                 &Displaying(|w: &mut dyn Write| ELEMENT_G[e3](
-                                    END_GRAPHIC_CENTER + from_angle_mag(tilt + arc * 2.0, r),
+                                    end_graphic_center() + from_angle_mag(tilt + arc * 2.0, r),
                                     scale,
                                     w
                                 )),
@@ -265,14 +279,19 @@ pub fn end_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                         })),
                         scores.clone(),
                         1,
-                        format!(
-                            "{} points for every triangle of adjacent {}, {} and {}",
-                            &scores, ELEMENT_NAMES[e1], ELEMENT_NAMES[e2], ELEMENT_NAMES[e3]
+                        tr!(
+                            "end-triangle",
+                            "{ $scores } points for every triangle of adjacent { $a }, { $b } and { $c }",
+                            scores = &scores,
+                            a = El(e1),
+                            b = El(e2),
+                            c = El(e3)
                         ),
                         vec![e1, e2, e3],
                         2,
                         false,
                     )
+                    .with_desire(Desire::Triangle(e1, e2, e3, 4.0))
                 }
             }),
         ),
@@ -290,17 +309,16 @@ pub fn end_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
             }),
             "8-1".into(),
             1,
-            format!("You care about every little thing. Score 8 points for each lake, 7 for each tomb, and so on; mountain:6, volcano:5, ice:4, forest:3, void:2, field:1, and score 2 points for very surviving agent."),
+            tr!("end-interventionist", "You care about every little thing. Score 8 points for each lake, 7 for each tomb, and so on; mountain:6, volcano:5, ice:4, forest:3, void:2, field:1, and score 2 points for very surviving agent."),
             vec![FIELD, FOREST, MOUNTAIN, VOLCANO, LAKE, ICE, TOMB, VOID], 2, true,
         )
+        // field forest mountain volcano lake ice tomb void
+        .with_desire(Desire::Weighted([1.0, 3.0, 6.0, 5.0, 8.0, 4.0, 7.0, 2.0]))
     })})});
 
     specs.push(CardGen { min_count: 2, desired_proportion: 0.0, generator: Box::new(IndexVec(vec![(ICE, FIELD, TOMB), (VOLCANO, MOUNTAIN, FOREST)]).into_map({
         let all_assets = all_assets.clone();
         move |(e1, eroad, e3)| {
-            let e1np = ELEMENT_NAMES_PLURAL[e1];
-            let e3np = ELEMENT_NAMES_PLURAL[e3];
-            let eroadn = ELEMENT_NAMES[eroad];
 
             let scores = "×".to_string();
 
@@ -315,19 +333,23 @@ pub fn end_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                     road_blob_rad(&all_assets, e1, e3, eroad, bounds, w);
                 }})),
                 format!("{}", &scores),1,
-                format!(
-                    "for all {e1np} and {e3np} on the banks of a clump of {eroadn}, score the number of {e1np} multiplied by the number of {e3np}"
+                tr!(
+                    "end-banks",
+                    "for all { $a-plural } and { $b-plural } on the banks of a clump of { $road }, score the number of { $a-plural } multiplied by the number of { $b-plural }",
+                    a = El(e1),
+                    b = El(e3),
+                    road = El(eroad)
                 ),
                 vec![e1,eroad,e3],
                 2,
                 true
             )
+            .with_desire(Desire::Banks(e1, eroad, e3))
         }
     }))});
 
     specs.push(CardGen { min_count: 8, desired_proportion: 0.0, generator: Box::new(IndexVec(vec![VOID, FOREST, VOLCANO]).into_map({let all_assets = all_assets.clone(); move|e|{
         let element_name = ELEMENT_NAMES[e];
-        let element_name_plural = ELEMENT_NAMES_PLURAL[e];
         CardSpec::end_card_with_back_blurred_message(
             &all_assets,
             format!("max_{}_cluster", element_name),
@@ -336,14 +358,15 @@ pub fn end_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                     w,
                     "{}{}",
                     &Displaying(|w:&mut dyn Write| big_splat(element_color_back(e), w)),
-                    &Displaying(|w:&mut dyn Write| ELEMENT_G[e](END_GRAPHIC_CENTER, 0.7, w)),
+                    &Displaying(|w:&mut dyn Write| ELEMENT_G[e](end_graphic_center(), 0.7, w)),
                 ).unwrap();
             })),
             "1".to_string(),1,
-            format!("1 point for every {element_name} in the single largest connected cluster of {element_name_plural} ({element_name_plural} outside of that cluster is valueless)"),
+            tr!("end-cluster", "1 point for every { $element } in the single largest connected cluster of { $element-plural } ({ $element-plural } outside of that cluster is valueless)", element = El(e)),
             vec![e],
             0, false
         )
+        .with_desire(Desire::LargestCluster(e))
     }}))});
 
     specs.push(CardGen {
@@ -353,7 +376,6 @@ pub fn end_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
             let all_assets = all_assets.clone();
             move |e| {
                 let ename = ELEMENT_NAMES[e];
-                let ename_plural = ELEMENT_NAMES_PLURAL[e];
                 let scores = "12".to_string();
                 CardSpec::end_card_with_back_blurred_message(
                     &all_assets,
@@ -363,9 +385,9 @@ pub fn end_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                         move |w: &mut dyn Write| {
                             assets
                                 .element(e)
-                                .centered_rad(END_GRAPHIC_CENTER, BIG_ELEMENT_RAD, w);
+                                .centered_rad(end_graphic_center(), BIG_ELEMENT_RAD, w);
                             assets.negatory.centered_rad(
-                                END_GRAPHIC_CENTER,
+                                end_graphic_center(),
                                 BIG_ELEMENT_RAD * 0.74,
                                 w,
                             );
@@ -373,14 +395,17 @@ pub fn end_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                     })),
                     scores.clone(),
                     1,
-                    format!(
-                        "{} points if there are no {ename_plural} at all",
-                        &scores
+                    tr!(
+                        "end-forbid",
+                        "{ $scores } points if there are no { $element-plural } at all",
+                        scores = &scores,
+                        element = El(e)
                     ),
                     vec![opposite_element(e)],
                     0,
                     false,
                 )
+                .with_desire(Desire::Absent(e, 12.0))
             }
         })),
     });
@@ -421,14 +446,18 @@ pub fn end_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                         })),
                         scores.clone(),
                         1,
-                        format!(
-                            "{} points if there is no {en1} adjacent to any {en2} at the end",
-                            &scores
+                        tr!(
+                            "end-never-adjacent",
+                            "{ $scores } points if there is no { $a } adjacent to any { $b } at the end",
+                            scores = &scores,
+                            a = El(e1),
+                            b = El(e2)
                         ),
                         vec![opposite_element(e1), opposite_element(e2)],
                         0,
                         false,
                     )
+                    .with_desire(Desire::NeverAdjacent(e1, e2, 10.0))
                 }
             }),
         ),
@@ -485,11 +514,18 @@ pub fn end_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                         })),
                         scores.clone(),
                         1,
-                        format!("{scores} points per {en1} that is not adjacent to {en2}",),
+                        tr!(
+                            "end-isolated",
+                            "{ $scores } points per { $a } that is not adjacent to { $b }",
+                            scores = &scores,
+                            a = El(e1),
+                            b = El(e2)
+                        ),
                         vec![e1, opposite_element(e2)],
                         0,
                         false,
                     )
+                    .with_desire(Desire::Isolated(e1, vec![e2], 3.0))
                 }
             }),
         ),
@@ -548,13 +584,19 @@ pub fn end_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                             })),
                             scores.clone(),
                             1,
-                            format!(
-                                "{scores} points per {en1} that is not adjacent to {en2} or {en3}",
+                            tr!(
+                                "end-isolated-2",
+                                "{ $scores } points per { $a } that is not adjacent to { $b } or { $c }",
+                                scores = &scores,
+                                a = El(e1),
+                                b = El(e2),
+                                c = El(e3)
                             ),
                             vec![e1, opposite_element(e2)],
                             2,
                             false,
                         )
+                        .with_desire(Desire::Isolated(e1, vec![e2, e3], 4.0))
                     }
                 }),
             ),
@@ -598,13 +640,13 @@ pub fn end_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
             String::from("scavenger"),
             3,
             "4".to_string(),
-            String::from("a terrible hunger.\n4 points for every corpse in your possession at the end (killing creates corposes, corpses can be stowed as items and carried around)"),
+            tr!("end-scavenger", "a terrible hunger.\n4 points for every corpse in your possession at the end (killing creates corposes, corpses can be stowed as items and carried around)"),
             vec![],
             0,
             false,
         ))),
     });
-    specs.push(CardGen { min_count: 8, desired_proportion: 0.0, generator: Box::new(Once(from_asset(&all_assets, &assets.altruism, String::from("altruism"), 2, "=".to_string(), String::from("Your values encompass the values of others.\n\nScore the sum of the scores of all other agencies"), vec![], 1, true)))});
+    specs.push(CardGen { min_count: 8, desired_proportion: 0.0, generator: Box::new(Once(from_asset(&all_assets, &assets.altruism, String::from("altruism"), 2, "=".to_string(), tr!("end-altruism", "Your values encompass the values of others.\n\nScore the sum of the scores of all other agencies"), vec![], 1, true)))});
 
     specs.push(CardGen {
         min_count: 8,
@@ -631,13 +673,17 @@ pub fn end_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                     }),
                     "1".to_string(),
                     1,
-                    format!(
-                        "1 point for every {e1n} or {e2n} in the largest connected patch of those land types."
+                    tr!(
+                        "end-patch",
+                        "1 point for every { $a } or { $b } in the largest connected patch of those land types.",
+                        a = El(e1),
+                        b = El(e2)
                     ),
                     vec![e1, e2],
                     2,
                     false,
                 )
+                .with_desire(Desire::LargestPatch(e1, e2))
             })
         }),
     });
@@ -656,7 +702,7 @@ pub fn end_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
             }
         })),
         "=".to_string(), 2,
-        "You share the desires of the players adjacent to you, but only when they're adjacent to you. As soon as you're apart, you will stop caring about those things.".to_string(),
+        tr!("end-dog-altruism", "You share the desires of the players adjacent to you, but only when they're adjacent to you. As soon as you're apart, you will stop caring about those things."),
         vec![],
         2,
         true
@@ -676,7 +722,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
         let element_name = ELEMENT_NAMES[e];
         CardSpec::means_card(
             &assets,
-            format!("{element_name} ambush"),
+            tr!("means-ambush-name", "{ $element } ambush", element = El(e)),
             Some(format!("ambush from {element_name}")),
             0, false, 1,
             vec![(Kill, vec![e])],
@@ -698,7 +744,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                     guy2_dead(&assets, c2, guyscale, w);
                 })
             },
-            format!("standing in {element_name}, kill one nearby agent"),
+            tr!("means-ambush", "standing in { $element }, kill one nearby agent", element = El(e)),
         )
     }}))});
 
@@ -708,7 +754,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
         let pair_name_escaped = pair_name.replace('/', " or ");
         CardSpec::means_card(
             &assets,
-            format!("slaying"),
+            tr!("means-slaying-name", "slaying"),
             Some(format!("slaying beside {pair_name_escaped}")),
             0, false, 1,
             vec![(Kill,vec![e.0, e.1])],
@@ -729,20 +775,18 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                     guy2_dead(&assets, bdc + V2::new(0.0, bdr*0.57), 1.0, w);
                 })
             },
-            format!("Kill an agent in the same land as you, when near to {pair_name}"),
+            tr!("means-slaying", "Kill an agent in the same land as you, when near to { $a }/{ $b }", a = El(e.0), b = El(e.1)),
         )
     }}))});
 
     r.push(CardGen { min_count: 8, desired_proportion: 0.0, generator: Box::new(IndexVec(vec![TOMB, MOUNTAIN]).into_map({
         let all_assets = all_assets.clone();
         move |e| {
-            let element_name = ELEMENT_NAMES[e];
             let opposite = opposite_element(e);
-            let opposite_name = ELEMENT_NAMES[opposite];
             let center = card_upper_center();
             CardSpec::means_card(
                 &all_assets,
-                format!("transit {element_name}"),
+                tr!("means-transit-name", "transit { $element }", element = El(e)),
                 None,
                 0, false, 1,
                 vec![(Change, vec![e])],
@@ -759,7 +803,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                         // all_assets.guy2.centered_rad(eyc, eyr, w);
                     }
                 }),
-                format!("standing nearby to {opposite_name}, flip it to {element_name}"),
+                tr!("means-transit-nearby", "standing nearby to { $opposite }, flip it to { $element }", opposite = El(opposite), element = El(e)),
             )
         }
     }))});
@@ -769,11 +813,10 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
         move |e| {
             let element_name = ELEMENT_NAMES[e];
             let opposite = opposite_element(e);
-            let opposite_name = ELEMENT_NAMES[opposite];
             let center = card_upper_center();
             CardSpec::means_card(
                 &all_assets,
-                format!("transit {element_name}"),
+                tr!("means-transit-name", "transit { $element }", element = El(e)),
                 Some(format!("transit any {element_name}")),
                 1, true, 1,
                 vec![(Change, vec![e])],
@@ -784,7 +827,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                         f.centered(center, 1.0, w); 
                     }
                 }),
-                format!("flip any {opposite_name} to {element_name}"),
+                tr!("means-transit-any", "flip any { $opposite } to { $element }", opposite = El(opposite), element = El(e)),
             )
         }
     }))});
@@ -792,12 +835,9 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
     r.push(CardGen { min_count: 8, desired_proportion: 0.0, generator: Box::new(IndexVec(vec![(VOLCANO, FOREST), (MOUNTAIN, LAKE), (FIELD, VOLCANO), (MOUNTAIN, VOID)]).into_map({
         let all_assets = all_assets.clone();
         move |(se, re)| {
-            let ring_name = ELEMENT_NAMES[re];
-            let ring_opposite_name_plural = ELEMENT_NAMES_PLURAL[opposite_element(re)];
-            let support_opposite_name = ELEMENT_NAMES[opposite_element(se)];
             CardSpec::means_card(
                 &all_assets,
-                format!("bloom {ring_name}"),
+                tr!("means-bloom-name", "bloom { $ring }", ring = El(re)),
                 None,
                 0, false, 1,
                 vec![(Change, vec![se, re])],
@@ -811,7 +851,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                         assets.guy2.by_anchor_rad(center, sd*0.13, w);
                     }
                 }),
-                format!("near to {support_opposite_name}, flip it, and flip all of the {ring_opposite_name_plural} near to you"),
+                tr!("means-bloom", "near to { $support }, flip it, and flip all of the { $ring-plural } near to you", support = El(opposite_element(se)), ring = El(opposite_element(re))),
             )
         }
     }))});
@@ -851,7 +891,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
         let bn = ELEMENT_NAMES[b];
         CardSpec::means_card(
             &all_assets,
-            format!("prism"),
+            tr!("means-prism-name", "prism"),
             Some(format!("prism {an} {bn}")),
             0, false, 1,
             vec![(Change, vec![])],
@@ -876,7 +916,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                     assets.triangle.by_grav(m, LEFT_MIDDLE, side, w);
                 }
             }),
-            format!("standing near a pair of {an} and {bn}, one land near to both lands on either side, or one of those lands of the pair, can be flipped"),
+            tr!("means-prism", "standing near a pair of { $a } and { $b }, one land near to both lands on either side, or one of those lands of the pair, can be flipped", a = El(a), b = El(b)),
         )
     }}))});
 
@@ -885,10 +925,9 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
         move |((e, o), supporting_element)| {
             let element_name = ELEMENT_NAMES[e];
             let supporting_element_name = ELEMENT_NAMES[supporting_element];
-            let opposite_name = ELEMENT_NAMES[o];
             CardSpec::means_card(
                 &all_assets,
-                format!("transit {element_name}/{opposite_name}"),
+                tr!("means-transit-either-name", "transit { $element }/{ $opposite }", element = El(e), opposite = El(o)),
                 Some(format!("transit either {element_name} {supporting_element_name}")),
                 1, false, 1,
                 vec![(Change, vec![e, o])],
@@ -911,7 +950,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                         );
                     }
                 }),
-                format!("standing in {supporting_element_name}, flip a nearby {opposite_name}/{element_name}"),
+                tr!("means-transit-either", "standing in { $support }, flip a nearby { $opposite }/{ $element }", support = El(supporting_element), opposite = El(o), element = El(e)),
             )
         }
     }))});
@@ -922,10 +961,9 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
             let element_name = ELEMENT_NAMES[e];
             let supporting_element_name = ELEMENT_NAMES[supporting_element];
             let opposite = opposite_element(e);
-            let opposite_name = ELEMENT_NAMES[opposite];
             CardSpec::means_card(
                 &all_assets,
-                format!("transit {element_name}"),
+                tr!("means-transit-name", "transit { $element }", element = El(e)),
                 Some(format!("transit {element_name} {supporting_element_name}")),
                 0, false, 1,
                 vec![(Change, vec![e])],
@@ -948,7 +986,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                         );
                     }
                 }),
-                format!("standing in {supporting_element_name}, flip a nearby {opposite_name} to {element_name}"),
+                tr!("means-transit-supported", "standing in { $support }, flip a nearby { $opposite } to { $element }", support = El(supporting_element), opposite = El(opposite), element = El(e)),
             )
         }
     }))});
@@ -959,12 +997,10 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
             let efn = ELEMENT_NAMES[ef];
             let etn = ELEMENT_NAMES[et];
             let eto = opposite_element(et);
-            let eton = ELEMENT_NAMES[eto];
             let efo = opposite_element(ef);
-            let efon = ELEMENT_NAMES[efo];
             CardSpec::means_card(
                 &assets,
-                format!("{etn} catastrophe"),
+                tr!("means-catastrophe-name", "{ $element } catastrophe", element = El(et)),
                 Some(format!("distance flip {efn} {etn}")),
                 2, false, 1,
                 vec![(Kill, vec![et]), (Change, vec![ef])],
@@ -991,7 +1027,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                         assets.kill_diamond.centered_rad(kill_zone_center, kill_diamond_radius, w);
                     }
                 }),
-                format!("Standing in {efon}, flip it, and flip a {eton} up to three lands away, killing everything near to the distant {eton}"),
+                tr!("means-catastrophe", "Standing in { $from }, flip it, and flip a { $to } up to three lands away, killing everything near to the distant { $to }", from = El(efo), to = El(eto)),
             )
         }
     }))});
@@ -1001,7 +1037,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
         let esn = ELEMENT_NAMES[es];
         CardSpec::means_card(
             &assets,
-            format!("dominion around {en}"),
+            tr!("means-dominion-around-name", "dominion around { $element }", element = El(e)),
             Some(format!("dominion around {en} {esn}")),
             0, false, 1,
             vec![(Kill, vec![e, es])],
@@ -1015,7 +1051,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                 asset.element(es).centered_rad(ac, ar, w);
                 asset.kill_diamond_around.centered(bounds.center(), 1.0, w);
             }}),
-            format!("kill any number of agents standing near to {en} that's adjacent to {esn}."),
+            tr!("means-dominion-around", "kill any number of agents standing near to { $element } that's adjacent to { $support }.", element = El(e), support = El(es)),
         )
     }}))});
     
@@ -1025,10 +1061,9 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
         generator: Box::new(IndexVec(vec![ICE]).into_map({
             let assets = all_assets.clone();
             move |e| {
-                let enp = ELEMENT_NAMES_PLURAL[e];
                 CardSpec::means_card(
                     &assets,
-                    format!("dominion over {enp}"),
+                    tr!("means-dominion-name", "dominion over { $element-plural }", element = El(e)),
                     None,
                     0,
                     false,
@@ -1045,7 +1080,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                                 .centered_rad(bounds.center(), br * 1.2, w);
                         }
                     }),
-                    format!("kill any number of agents standing on any {enp}."),
+                    tr!("means-dominion", "kill any number of agents standing on any { $element-plural }.", element = El(e)),
                 )
             }
         })),
@@ -1057,7 +1092,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
         let escaped_enp = enp.replace('/', " or ");
         CardSpec::means_card(
             &assets,
-            format!("dominion over {enp}"),
+            tr!("means-dominion-either-name", "dominion over { $a }/{ $b }", a = El(e), b = El(eo)),
             Some(format!("dominion over {escaped_enp} beside {an}")),
             0, false, 1,
             vec![(Kill, vec![e, eo, a])],
@@ -1071,7 +1106,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                 asset.kill_diamond.centered_rad(bounds.center(), br*1.2, w);
                 asset.element(a).centered_rad(ac, ar, w);
             }}),
-            format!("kill any number of agents standing on any {enp} that's adjacent to {an}."),
+            tr!("means-dominion-either", "kill any number of agents standing on any { $a }/{ $b } that's adjacent to { $support }.", a = El(e), b = El(eo), support = El(a)),
         )
     }}))});
 
@@ -1080,7 +1115,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
         let esn = ELEMENT_NAMES[es];
         CardSpec::means_card(
             &assets,
-            format!("tyranny"),
+            tr!("means-tyranny-name", "tyranny"),
             Some(format!("tyranny {ekn} {esn}")),
             0, true, 1,
             vec![(Kill, vec![es, ek])],
@@ -1100,18 +1135,17 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                 asset.double_diamond.centered_rad(bc, dr, w);
                 guy2(&asset, bc, 1.0, w);
             }}),
-            format!("standing in {ekn}, near to {esn}, kill an agent within two steps of where you stand."),
+            tr!("means-tyranny", "standing in { $element }, near to { $support }, kill an agent within two steps of where you stand.", element = El(ek), support = El(es)),
         )
     }}))});
 
     r.push(CardGen { min_count: 8, desired_proportion: 0.0, generator: Box::new(IndexVec(vec![(ICE, ICE), (VOLCANO, VOID)]).into_map({let assets=all_assets.clone(); move |(ek, es)|{
         let ekn = ELEMENT_NAMES[ek];
         let est = opposite_element(es);
-        let estn = ELEMENT_NAMES[est];
         let esn = ELEMENT_NAMES[es];
         CardSpec::means_card(
             &assets,
-            format!("tyrant shot"),
+            tr!("means-tyrant-shot-name", "tyrant shot"),
             Some(format!("tyrant shot {ekn} {esn}")),
             2, false, 1,
             vec![(Kill, vec![ek]), (Change, vec![est])],
@@ -1131,7 +1165,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                 asset.double_diamond.centered_rad(bc, dr, w);
                 guy2(&asset, bc, 1.0, w);
             }}),
-            format!("standing in {ekn} near to {estn}, kill an agent within two steps of where you stand, flipping the {estn}."),
+            tr!("means-tyrant-shot", "standing in { $element } near to { $support }, kill an agent within two steps of where you stand, flipping the { $support }.", element = El(ek), support = El(est)),
         )
     }}))});
 
@@ -1145,7 +1179,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                 let esn = ELEMENT_NAMES[es];
                 CardSpec::means_card(
                     &assets,
-                    format!("domain"),
+                    tr!("means-domain-name", "domain"),
                     Some(format!("domain {ekn} {esn}")),
                     0,
                     true,
@@ -1184,8 +1218,11 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                             guy2(&asset, bc, 1.0, w);
                         }
                     }),
-                    format!(
-                        "standing in {ekn} near to {esn}, flip a land within a 2 land radius."
+                    tr!(
+                        "means-domain",
+                        "standing in { $element } near to { $support }, flip a land within a 2 land radius.",
+                        element = El(ek),
+                        support = El(es)
                     ),
                 )
             }
@@ -1200,10 +1237,9 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
             move |(ek, es)| {
                 let ekn = ELEMENT_NAMES[ek];
                 let esn = ELEMENT_NAMES[es];
-                let eson = ELEMENT_NAMES[opposite_element(es)];
                 CardSpec::means_card(
                     &assets,
-                    format!("domain burn"),
+                    tr!("means-domain-burn-name", "domain burn"),
                     Some(format!("domain burn {ekn} {esn}")),
                     0,
                     false,
@@ -1242,8 +1278,11 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                             guy2(&asset, bc, 1.0, w);
                         }
                     }),
-                    format!(
-                        "standing in {ekn} near to {eson}, flip the supporting {eson}, and flip any other land within a 2 land radius."
+                    tr!(
+                        "means-domain-burn",
+                        "standing in { $element } near to { $support }, flip the supporting { $support }, and flip any other land within a 2 land radius.",
+                        element = El(ek),
+                        support = El(opposite_element(es))
                     ),
                 )
             }
@@ -1257,10 +1296,9 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
             let assets = all_assets.clone();
             move |ek| {
                 let ekn = ELEMENT_NAMES[ek];
-                let ekon = ELEMENT_NAMES[opposite_element(ek)];
                 CardSpec::means_card(
                     &assets,
-                    format!("hot cast"),
+                    tr!("means-hot-cast-name", "hot cast"),
                     Some(format!("domain flip {ekn}")),
                     0,
                     false,
@@ -1291,8 +1329,10 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                             guy2(&asset, bc, 1.0, w);
                         }
                     }),
-                    format!(
-                        "standing in {ekon}, flip it, and flip one land within a 2 land radius."
+                    tr!(
+                        "means-hot-cast",
+                        "standing in { $element }, flip it, and flip one land within a 2 land radius.",
+                        element = El(opposite_element(ek))
                     ),
                 )
             }
@@ -1309,7 +1349,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                 let esn = ELEMENT_NAMES[es];
                 CardSpec::means_card(
                     &assets,
-                    format!("domain"),
+                    tr!("means-domain-name", "domain"),
                     Some(format!("domain smaller {ekn} {esn}")),
                     0,
                     false,
@@ -1349,7 +1389,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                             guy2(&asset, bc, 1.0, w);
                         }
                     }),
-                    format!("standing in {ekn} near {esn}, flip a nearby land."),
+                    tr!("means-domain-smaller", "standing in { $element } near { $support }, flip a nearby land.", element = El(ek), support = El(es)),
                 )
             }
         })),
@@ -1365,7 +1405,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                 let esn = ELEMENT_NAMES[es];
                 CardSpec::means_card(
                     &assets,
-                    format!("domain burn"),
+                    tr!("means-domain-burn-name", "domain burn"),
                     Some(format!("domain smaller exchange {ekn} {esn}")),
                     0,
                     false,
@@ -1405,8 +1445,11 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                             guy2(&asset, bc, 1.0, w);
                         }
                     }),
-                    format!(
-                        "standing in {ekn} near {esn}, flip the {esn}, and flip any one other nearby land."
+                    tr!(
+                        "means-domain-smaller-exchange",
+                        "standing in { $element } near { $support }, flip the { $support }, and flip any one other nearby land.",
+                        element = El(ek),
+                        support = El(es)
                     ),
                 )
             }
@@ -1420,10 +1463,9 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
             let assets = all_assets.clone();
             move |ek| {
                 let ekn = ELEMENT_NAMES[ek];
-                let eka = ELEMENT_ARTICLE[ek];
                 CardSpec::means_card(
                     &assets,
-                    format!("domain"),
+                    tr!("means-domain-name", "domain"),
                     Some(format!("domain {ekn}")),
                     0,
                     true,
@@ -1459,14 +1501,13 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                             guy2(&asset, bc, 1.0, w);
                         }
                     }),
-                    format!("standing on {eka} {ekn}, flip a land within a 2 land radius."),
+                    tr!("means-domain-any", "standing on { $element-article } { $element }, flip a land within a 2 land radius.", element = El(ek)),
                 )
             }
         })),
     });
 
     r.push(CardGen { min_count: 8, desired_proportion: 0.0, generator: Box::new(IndexVec(vec![MOUNTAIN, VOID]).into_map({let all_assets=all_assets.clone(); move |e|{
-        let element_name = ELEMENT_NAMES[e];
         let opposite = opposite_element(e);
         let opposite_name = ELEMENT_NAMES[opposite];
         let bounds = means_graphic_usual_bounds_shrunk_appropriately();
@@ -1481,7 +1522,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
         
         CardSpec::means_card(
             &all_assets,
-            format!("{opposite_name} wind"),
+            tr!("means-wind-name", "{ $element } wind", element = El(opposite)),
             Some(format!("wind flip {opposite_name}")),
             2, false, 1,
             vec![(Change, vec![e]), (Move, vec![e])],
@@ -1499,7 +1540,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                 // assets.blank.centered_rad(gc, ger, w);
                 assets.guy2.by_anchor_rad(gc, ger*0.9, w);
             }}),
-            format!("flip a nearby {opposite_name} to {element_name}, moving each occupant of that land up to three lands"),
+            tr!("means-wind-flip", "flip a nearby { $opposite } to { $element }, moving each occupant of that land up to three lands", opposite = El(opposite), element = El(e)),
         )
     }}))});
     
@@ -1518,7 +1559,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                     let ben = ELEMENT_NAMES[be];
                     CardSpec::means_card(
                         &assets,
-                        "reaction wind".to_string(),
+                        tr!("means-reaction-wind-name", "reaction wind"),
                         Some(format!("wind_both_{aen}_{ben}")),
                         1,
                         // the ones that just sort of move an element along through a limited substrate without changing the total number of either are extraordinarily weak
@@ -1549,7 +1590,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                                 do_arr(arb + V2::new(0.0, -2.0*arsyh));
                             }
                         }),
-                        format!("move any one agent standing near to a pair of {aen} and {ben} by three lands."),
+                        tr!("means-reaction-wind", "move any one agent standing near to a pair of { $a } and { $b } by three lands.", a = El(ae), b = El(be)),
                     )
                 }
             }),
@@ -1557,7 +1598,6 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
     });
     
     r.push(CardGen { min_count: 8, desired_proportion: 0.0, generator: Box::new(IndexVec(vec![FIELD]).into_map({let all_assets=all_assets.clone(); move |e|{
-        let element_name = ELEMENT_NAMES[e];
         let opposite = opposite_element(e);
         let opposite_name = ELEMENT_NAMES[opposite];
         let bounds = means_graphic_usual_bounds_shrunk_appropriately();
@@ -1570,7 +1610,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
         
         CardSpec::means_card(
             &all_assets,
-            format!("{opposite_name} wind"),
+            tr!("means-wind-name", "{ $element } wind", element = El(opposite)),
             Some(format!("wind {opposite_name}")),
             2, false, 1,
             vec![(Change, vec![e]), (Move, vec![e])],
@@ -1588,7 +1628,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                 // assets.blank.centered_rad(gc, ger, w);
                 assets.guy2.by_anchor_rad(gc, ger*0.9, w);
             }}),
-            format!("flip a nearby {opposite_name} to {element_name}, moving each occupant of that land up to two lands"),
+            tr!("means-wind-flip-smaller", "flip a nearby { $opposite } to { $element }, moving each occupant of that land up to two lands", opposite = El(opposite), element = El(e)),
         )
     }}))});
     
@@ -1607,7 +1647,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
         
         CardSpec::means_card(
             &all_assets,
-            format!("{element_name} wind"),
+            tr!("means-wind-name", "{ $element } wind", element = El(e)),
             Some(format!("wind {element_name}")),
             1, false, 1,
             vec![(Move, vec![e])],
@@ -1625,13 +1665,12 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                 // assets.blank.centered_rad(gc, ger, w);
                 assets.guy2.by_anchor_rad(gc, ger*0.9, w);
             }}),
-            format!("move each occupant of a nearby {element_name} up to three lands"),
+            tr!("means-wind", "move each occupant of a nearby { $element } up to three lands", element = El(e)),
         )
     }}))});
     
     r.push(CardGen { min_count: 8, desired_proportion: 0.0, generator: Box::new(IndexVec(vec![MOUNTAIN]).into_map({let all_assets=all_assets.clone(); move |e|{
         let element_name = ELEMENT_NAMES[e];
-        let element_article = ELEMENT_ARTICLE[e];
         let bounds = means_graphic_usual_bounds_shrunk_appropriately();
         
         let sd = bounds.span().x;
@@ -1643,7 +1682,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
         
         CardSpec::means_card(
             &all_assets,
-            format!("{element_name} wind"),
+            tr!("means-wind-name", "{ $element } wind", element = El(e)),
             Some(format!("wind {element_name}")),
             1, false, 1,
             vec![(Move, vec![e])],
@@ -1655,7 +1694,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                 // do_arr(V2::new(bc.x + ard, aby - arsep));
                 do_arr(V2::new(bc.x, aby - arsep*2.0));
             }}),
-            format!("move each occupant of {element_article} {element_name} up to two lands"),
+            tr!("means-wind-any", "move each occupant of { $element-article } { $element } up to two lands", element = El(e)),
         )
     }}))});
     
@@ -1672,7 +1711,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
         
         CardSpec::means_card(
             &all_assets,
-            format!("freezing wind"),
+            tr!("means-freezing-wind-name", "freezing wind"),
             Some(format!("wind {element_name}")),
             1, false, 1,
             vec![(Change, vec![e]), (Move, vec![e])],
@@ -1684,7 +1723,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                 do_arr(V2::new(bc.x + ard, aby - arsep));
                 do_arr(V2::new(bc.x - ard, aby - arsep*2.0));
             }}),
-            format!("freeze any lake, moving its occupants up to three lands over"),
+            tr!("means-freezing-wind", "freeze any { $lake }, moving its occupants up to three lands over", lake = El(LAKE)),
         )
     }}))});
 
@@ -1695,10 +1734,9 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
             let assets = all_assets.clone();
             move |e| {
                 let en = ELEMENT_NAMES[e];
-                let ea = ELEMENT_ARTICLE[e];
                 CardSpec::means_card(
                     &assets,
-                    format!("{en} travel"),
+                    tr!("means-travel-name", "{ $element } travel", element = El(e)),
                     Some(format!("travel {en}")),
                     0,
                     false,
@@ -1717,7 +1755,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                             );
                         }
                     }),
-                    format!("send an agent standing on {ea} {en} to any other {en}"),
+                    tr!("means-travel", "send an agent standing on { $element-article } { $element } to any other { $element }", element = El(e)),
                 )
             }
         })),
@@ -1726,11 +1764,10 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
     r.push(CardGen { min_count: 8, desired_proportion: 0.0, generator: Box::new(IndexVec(vec![(VOLCANO, TOMB), (ICE, LAKE), (VOID, MOUNTAIN)]).into_map({let all_assets = all_assets.clone(); move|(e,et)|{
         let en = ELEMENT_NAMES[e];
         let eo = opposite_element(e);
-        let eon = ELEMENT_NAMES[eo];
         let etn = ELEMENT_NAMES[et];
         CardSpec::means_card(
             &all_assets,
-            format!("{eon} liftoff"),
+            tr!("means-liftoff-name", "{ $element } liftoff", element = El(eo)),
             Some(format!("liftoff {en} {etn}")),
             0, false, 1,
             vec![(Change, vec![e]), (Move, vec![e, et])],
@@ -1747,7 +1784,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                     );
                 }
             }),
-            format!("pick any {eon}, and send anyone standing on it to any {etn}, flipping the originating {eon}"),
+            tr!("means-liftoff", "pick any { $from }, and send anyone standing on it to any { $to }, flipping the originating { $from }", from = El(eo), to = El(et)),
         )
     }}))});
     
@@ -1756,7 +1793,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
         // let etn = ELEMENT_NAMES[es];
         CardSpec::means_card(
             &all_assets,
-            format!("{en} banishment"),
+            tr!("means-banishment-name", "{ $element } banishment", element = El(e)),
             Some(format!("sending {en}")),
             0, false, 1,
             vec![(Move, vec![e])],
@@ -1774,7 +1811,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                     );
                 }
             }),
-            format!("send anyone on a nearby land to any {en}"),
+            tr!("means-banishment", "send anyone on a nearby land to any { $element }", element = El(e)),
         )
     }}))});
     
@@ -1822,12 +1859,9 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                     let aen = ELEMENT_NAMES[ae];
                     let ben = ELEMENT_NAMES[be];
                     let cen = ELEMENT_NAMES[ce];
-                    let aeon = ELEMENT_NAMES[opposite_element(ae)];
-                    let beon = ELEMENT_NAMES[opposite_element(be)];
-                    let ceon = ELEMENT_NAMES[opposite_element(ce)];
                     CardSpec::means_card(
                         &assets,
-                        "flip all".to_string(),
+                        tr!("means-flip-all-name", "flip all"),
                         Some(format!("flip_all_{aen}_{ben}_{cen}")),
                         2,
                         true,
@@ -1849,7 +1883,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
 
                                 let tilt = -TAU / 24.0 + TAU / 2.0;
                                 let arc = TAU / 3.0;
-                                let r = GRAPHIC_RAD * 0.5;
+                                let r = graphic_rad() * 0.5;
                                 let scale = 0.5;
                                 let bounds = means_graphic_usual_bounds_shrunk_appropriately();
                                 let c = bounds.center();
@@ -1859,7 +1893,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                                 fce.centered(c + from_angle_mag(tilt + arc * 2.0, r), scale, w);
                             }
                         }),
-                        format!("standing on any chain of {aeon}, {beon}, {ceon}, flip all of them."),
+                        tr!("means-flip-all", "standing on any chain of { $a }, { $b }, { $c }, flip all of them.", a = El(opposite_element(ae)), b = El(opposite_element(be)), c = El(opposite_element(ce))),
                     )
                 }
             }),
@@ -1884,7 +1918,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                     let beon = ELEMENT_NAMES[opposite_element(be)];
                     CardSpec::means_card(
                         &assets,
-                        "flip both".to_string(),
+                        tr!("means-flip-both-name", "flip both"),
                         Some(format!("flip_both_{aeon}_{beon}")),
                         1,
                         // the ones that just sort of move an element along through a limited substrate without changing the total number of either are extraordinarily weak
@@ -1906,7 +1940,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                                 );
                             }
                         }),
-                        format!("When standing on a pair of {aeon} and {beon}, flip them."),
+                        tr!("means-flip-both", "When standing on a pair of { $a } and { $b }, flip them.", a = El(opposite_element(ae)), b = El(opposite_element(be))),
                     )
                 }
             }),
@@ -1917,7 +1951,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
         let ename = ELEMENT_NAMES[e];
         CardSpec::means_card(
             &all_assets,
-            "atoms for something else".to_string(), Some(format!("atoms {ename}")),
+            tr!("means-atoms-name", "atoms for something else"), Some(format!("atoms {ename}")),
             2, true, 1,
             vec![(Kill, vec![e])],
             Rc::new({
@@ -1934,7 +1968,7 @@ pub fn means_specs(all_assets: &Rc<Assets>) -> Vec<CardGen> {
                     horizontal_flip(&all_assets.cubed_guy2).by_anchor(c + V2::new(lateral, 0.0), guy_scale, w);
                 }
             }),
-            format!("Near to {ename} that is also near to an opponent's agent, capture that agent and replace it with a spare of your own. All of your agents can now use that player's abilities."),
+            tr!("means-atoms", "Near to { $element } that is also near to an opponent's agent, capture that agent and replace it with a spare of your own. All of your agents can now use that player's abilities.", element = El(e)),
         )
     })})});
     
@@ -1973,7 +2007,7 @@ pub fn land_specs_smaller(assets: &Rc<Assets>, repeating: &[u8]) -> Vec<CardGen>
     land_specs_dims(assets, repeating, MINI_HEX_DIMS, land_hex_smaller_bounds(), false)
 }
 pub fn land_specs_card(assets: &Rc<Assets>, repeating: &[u8]) -> Vec<CardGen> {
-    land_specs_dims(assets, repeating, CARD_DIMENSIONS, cutline_bounds(), true)
+    land_specs_dims(assets, repeating, card_dimensions(), cutline_bounds(), true)
 }
 pub fn land_specs_mini_circles(assets: &Rc<Assets>, repeating: &[u8]) -> Vec<CardGen> {
     land_specs_dims(assets, repeating, MINI_CIRCLE_DIMS, land_circle_bounds(), false)
@@ -1986,7 +2020,7 @@ pub fn land_specs_dims(assets: &Rc<Assets>, repeating: &[u8], dims: V2, bounds:R
         Rc::new(move |w: &mut dyn Write| {
             svg_outer(
                 dims,
-                element_color_back(e),
+                printed_background(element_color_back(e)),
                 &Displaying(
                     {let assets = assets.clone(); let bounds=bounds.clone(); move |w| {
                         assets
@@ -2008,10 +2042,14 @@ pub fn land_specs_dims(assets: &Rc<Assets>, repeating: &[u8], dims: V2, bounds:R
                 name: format!("land_{}_{}", ELEMENT_NAMES[e], ELEMENT_NAMES[eo]),
                 repeat: repeatings[e / 2] as usize,
                 level: 0,
+                clown: false,
+                deck: Deck::Land,
                 frequency_modifier: 1.0,
                 properties: vec![],
                 generate_front: side(assets.clone(), e, dims, bounds.clone(), rotated),
                 generate_back: side(assets.clone(), eo, dims, bounds.clone(), rotated),
+                desire: None,
+                description: String::new(),
             }
         })),
     });
@@ -2061,53 +2099,46 @@ pub fn weights_to_cuts(weights: &[f64], seats: usize) -> Vec<usize> {
     }
 }
 
+impl BoardParams {
+    // the same params always deal the same board
+    pub fn deal(&self) -> DealtBoard {
+        assert_eq!(
+            self.weights.len(),
+            4,
+            "a board needs a weight for each of the 4 land pairs, got {:?}",
+            self.weights
+        );
+        let cuts = weights_to_cuts(&self.weights, hexagon_tile_count(self.radius));
+        let mut rng = rand::rngs::StdRng::seed_from_u64(self.seed);
+        deal_board(&cuts, self.radius, self.suppress_voids, &self.constraints, &mut rng)
+    }
+}
+
 pub fn generate_board(
     assets: &Rc<Assets>,
-    weights: &Vec<f64>,
-    radius: usize,
-    suppress_voids: bool,
-    seed: u64,
+    params: &BoardParams,
+    annotations: &BoardAnnotations,
     w: &mut dyn Write,
 ) {
+    let BoardParams {
+        weights,
+        radius,
+        suppress_voids,
+        seed,
+        constraints,
+    } = params;
+    let (radius, suppress_voids, seed) = (*radius, *suppress_voids, *seed);
+    let code = params.code();
     // let lc = land_specs(assets);
     // let cards: Vec<CardSpec> = lc.into_iter().next().unwrap().collect();
     // forest/field, mountain/volcano, lake/ice, tomb/void
-    // figure out the card counts based on the weights
-    let count = {
-        let mut t = 1;
-        for i in 0..radius {
-            t += (i + 1) * 6;
-        }
-        t
-    };
-    let cuts = weights_to_cuts(&weights, count);
-    fn shuffled_land_tiles(
-        land_card_counts: &Vec<usize>,
-        suppress_voids: bool,
-        rng: &mut impl Rng,
-    ) -> Vec<ElementTag> {
-        let mut rng = RefCell::new(rng);
-        let mut r: Vec<usize> = land_card_counts
-            .iter()
-            .enumerate()
-            .flat_map(|(i, c)| {
-                (0..*c).map({
-                    let mut rng = rng.borrow_mut();
-                    move |_| {
-                        if (suppress_voids && i == 3) || rng.gen_bool(0.5) {
-                            i * 2
-                        } else {
-                            i * 2 + 1
-                        }
-                    }
-                })
-            })
-            .collect();
-        (&mut r).shuffle::<_>(rng.get_mut());
-        r
+    let dealt = params.deal();
+    if !dealt.satisfied {
+        println!(
+            "warning, board {code} couldn't satisfy its constraints after {} shuffles and {} repair steps",
+            dealt.attempts, dealt.repair_steps
+        );
     }
-    let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
-    let cards = shuffled_land_tiles(&cuts, suppress_voids, &mut rng);
 
     let sep = BIG_ELEMENT_RAD * 2.25;
     let span = both_dims(sep * (1 + 1 + 2 * radius) as f64);
@@ -2115,16 +2146,130 @@ pub fn generate_board(
     do_sheet(
         span,
         &Displaying(|w| {
-            for (p, e) in HexSpiral::new()
-                .layer_iter(radius)
-                .map(|c| hexify(c.to_v2()))
-                .zip(cards.iter())
-            {
-                assets
-                    .element(*e)
-                    .centered_rad(p.yx() * sep + center, BIG_ELEMENT_RAD, w);
+            // so that anyone holding the svg can deal the same board again
+            write!(
+                w,
+                "<desc id=\"boardparameters\">code: {code}, seed: {seed}, radius: {radius}, weights: {weights:?}, suppress_voids: {suppress_voids}, constraints: {constraints:?}, attempts: {}, repair_steps: {}, satisfied: {}</desc>",
+                dealt.attempts, dealt.repair_steps, dealt.satisfied
+            )
+            .unwrap();
+            write!(
+                w,
+                r##"<text x="{}" y="{}" style="font-weight:500;font-size:{}px;font-family:Rubik;text-anchor:end;fill:#3e3e3e">{code}</text>"##,
+                span.x - sep * 0.3,
+                span.y - sep * 0.3,
+                sep * 0.3
+            )
+            .unwrap();
+            let board = &dealt.board;
+            let at = |c: Coord| hexify(c.to_v2()).yx() * sep + center;
+            let highlight = annotations.highlight.as_ref().map(|d| d.evaluate(board));
+            if let Some(score) = highlight.as_ref() {
+                // links go under the rings so that a cluster reads as one shape
+                for (i, j) in board.adjacent_pairs() {
+                    if score.tiles.contains(&i) && score.tiles.contains(&j) {
+                        let (a, b) = (at(board.tiles[i].0), at(board.tiles[j].0));
+                        write!(
+                            w,
+                            r##"<path d="M {},{} L {},{}" style="fill:none;stroke:#{HIGHLIGHT_COLOR};stroke-width:{};stroke-linecap:round"/>"##,
+                            a.x, a.y, b.x, b.y, BIG_ELEMENT_RAD * 0.7
+                        )
+                        .unwrap();
+                    }
+                }
+                for i in score.tiles.iter() {
+                    let p = at(board.tiles[*i].0);
+                    write!(
+                        w,
+                        r##"<circle cx="{}" cy="{}" r="{}" style="fill:#{HIGHLIGHT_COLOR}"/>"##,
+                        p.x, p.y, BIG_ELEMENT_RAD * 1.12
+                    )
+                    .unwrap();
+                }
+            }
+            for (c, e) in board.tiles.iter() {
+                assets.element(*e).centered_rad(at(*c), BIG_ELEMENT_RAD, w);
+            }
+            if annotations.coordinates {
+                for (c, _) in board.tiles.iter() {
+                    let p = at(*c) + V2::new(0.0, BIG_ELEMENT_RAD * 1.02);
+                    write!(
+                        w,
+                        r##"<text x="{}" y="{}" style="font-weight:500;font-size:{}px;font-family:Rubik;text-anchor:middle;fill:#3e3e3e">{},{}</text>"##,
+                        p.x, p.y, sep * 0.14, c.x, c.y
+                    )
+                    .unwrap();
+                }
+            }
+            // agents sharing a tile stand side by side
+            // in the order they were given, so that the same annotations always draw the same svg
+            let mut standing: Vec<(Coord, Vec<&BoardAgent>)> = Vec::new();
+            for a in annotations.agents.iter() {
+                match standing.iter_mut().find(|(c, _)| *c == a.at) {
+                    Some((_, here)) => here.push(a),
+                    None => standing.push((a.at, vec![a])),
+                }
+            }
+            for (c, agents) in standing {
+                let spacing = BIG_ELEMENT_RAD * 0.85;
+                let left = -(agents.len() as f64 - 1.0) * spacing / 2.0;
+                for (k, a) in agents.iter().enumerate() {
+                    let team = TEAM_COLORS[a.team % TEAM_COLORS.len()];
+                    let (asset, from) = match a.kind {
+                        AgentKind::Guy => (&assets.guy2, GUY2_BODY_COLOR),
+                        AgentKind::Mage => (&assets.guy2_mage, GUY2_BODY_COLOR),
+                        AgentKind::Dead => (&assets.dead_guy2, DEAD_GUY2_BODY_COLOR),
+                    };
+                    // feet a little below the middle of the tile
+                    let feet = at(c) + V2::new(left + k as f64 * spacing, BIG_ELEMENT_RAD * 0.45);
+                    recolored(asset, from, team).by_anchor(
+                        feet,
+                        BIG_ELEMENT_SPAN * 0.65 / assets.guy2.anchor.y,
+                        w,
+                    );
+                }
+            }
+            if let Some(score) = highlight.as_ref() {
+                write!(
+                    w,
+                    r##"<text x="{}" y="{}" style="font-weight:500;font-size:{}px;font-family:Rubik;fill:#3e3e3e">scores {}</text>"##,
+                    sep * 0.3,
+                    span.y - sep * 0.3,
+                    sep * 0.3,
+                    score.points
+                )
+                .unwrap();
             }
         }),
         w,
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn board_agents_wear_their_team_colors() {
+        let assets = Rc::new(Assets::load(Path::new("assets")));
+        let params = BoardParams {
+            weights: vec![1.0, 1.0, 1.0, 1.0],
+            radius: 1,
+            suppress_voids: false,
+            seed: 0,
+            constraints: BoardConstraints::default(),
+        };
+        let annotations = BoardAnnotations {
+            agents: vec![
+                BoardAgent { at: Coord::new(0, 0), kind: AgentKind::Guy, team: 0 },
+                BoardAgent { at: Coord::new(1, 0), kind: AgentKind::Dead, team: 1 },
+            ],
+            ..BoardAnnotations::default()
+        };
+        let mut out = Vec::new();
+        generate_board(&assets, &params, &annotations, &mut out);
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains(&format!("#{}", TEAM_COLORS[0])));
+        assert!(out.contains(&format!("#{}", TEAM_COLORS[1])));
+    }
+}
@@ -0,0 +1,242 @@
+// translations of the card texts. English is written in the code, every other language has a catalog, lang/de.ftl for `--lang de`. Catalogs are in a small subset of fluent's syntax, messages and comments:
+//
+//   # a comment
+//   end-each = { $scores } Punkt für jedes { $element }
+//   end-altruism = Deine Werte umfassen die Werte der anderen.
+//
+//       Zähle die Summe der Punkte aller anderen.
+//
+// (indented lines continue the message above, joined by newlines.) Element arguments can be given in any form that the catalog declares for that element, `{ $element-dative }` reads element-field-dative for a field, falling back to plain element-field. English has the forms plural, singular and article (see ELEMENT_NAMES_PLURAL etc). Braces that don't start with a $ are left alone, so {kill} icons work in translations too.
+// Messages a catalog doesn't have are left in English, with a warning.
+
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use super::*;
+
+pub struct Catalog {
+    pub lang: String,
+    messages: HashMap<String, String>,
+    // so that each missing message is only reported once
+    reported_missing: RefCell<HashSet<String>>,
+}
+impl Catalog {
+    pub fn load(path: &Path, lang: &str) -> Self {
+        let text = std::fs::read_to_string(path)
+            .unwrap_or_else(|e| panic!("couldn't read the catalog for {lang} at {}: {e}", path.display()));
+        let mut messages: HashMap<String, String> = HashMap::new();
+        let mut current: Option<String> = None;
+        for (i, line) in text.lines().enumerate() {
+            if line.trim_start().starts_with('#') {
+                continue;
+            }
+            if line.starts_with([' ', '\t']) || line.trim().is_empty() {
+                if let Some(id) = &current {
+                    let m = messages.get_mut(id).unwrap();
+                    // fluent drops the blank lines at the end of a message, and the indentation
+                    m.push('\n');
+                    m.push_str(line.trim());
+                }
+                continue;
+            }
+            let Some((id, value)) = line.split_once('=') else {
+                panic!("{}:{}: expected `id = message`", path.display(), i + 1);
+            };
+            let id = id.trim().to_string();
+            messages.insert(id.clone(), value.trim().to_string());
+            current = Some(id);
+        }
+        for m in messages.values_mut() {
+            let trimmed = m.trim_end().trim_start_matches('\n').to_string();
+            *m = trimmed;
+        }
+        Self {
+            lang: lang.to_string(),
+            messages,
+            reported_missing: RefCell::new(HashSet::new()),
+        }
+    }
+}
+
+thread_local! {
+    static CATALOG: RefCell<Option<Rc<Catalog>>> = const { RefCell::new(None) };
+}
+// "en" goes back to the English in the code
+pub fn set_lang(lang: &str) {
+    let catalog = if lang == "en" {
+        None
+    } else {
+        Some(Rc::new(Catalog::load(
+            &Path::new("lang").join(format!("{lang}.ftl")),
+            lang,
+        )))
+    };
+    CATALOG.with(|c| *c.borrow_mut() = catalog);
+}
+fn catalog() -> Option<Rc<Catalog>> {
+    CATALOG.with(|c| c.borrow().clone())
+}
+pub fn lang() -> String {
+    catalog().map_or("en".to_string(), |c| c.lang.clone())
+}
+
+// where generated output goes, so that each language's (and card format's) decks end up separate. English mini cards are written where they always were, other languages go in localized/{lang}/, other formats in formats/{format}/ within that.
+pub fn localized(path: impl AsRef<Path>) -> PathBuf {
+    let mut dir = match catalog() {
+        None => PathBuf::new(),
+        Some(c) => Path::new("localized").join(&c.lang),
+    };
+    if card_format() != MINI_CARDS {
+        dir = dir.join("formats").join(card_format().name);
+    }
+    if dir.as_os_str().is_empty() {
+        return path.as_ref().to_path_buf();
+    }
+    std::fs::create_dir_all(&dir).unwrap();
+    dir.join(path)
+}
+
+pub enum TrArg {
+    Text(String),
+    Element(ElementTag),
+}
+// marks an argument to tr! as an element, so that the catalog can decline it
+pub struct El(pub ElementTag);
+impl From<El> for TrArg {
+    fn from(e: El) -> Self {
+        TrArg::Element(e.0)
+    }
+}
+impl From<String> for TrArg {
+    fn from(s: String) -> Self {
+        TrArg::Text(s)
+    }
+}
+impl From<&String> for TrArg {
+    fn from(s: &String) -> Self {
+        TrArg::Text(s.clone())
+    }
+}
+impl From<&str> for TrArg {
+    fn from(s: &str) -> Self {
+        TrArg::Text(s.to_string())
+    }
+}
+
+// tr!("end-each", "{ $scores } point for every { $element-singular }", scores = scores, element = El(e))
+#[macro_export]
+macro_rules! tr {
+    ($id:literal, $english:literal $(, $arg:ident = $value:expr)* $(,)?) => {
+        $crate::translate($id, $english, &[$((stringify!($arg), $crate::TrArg::from($value))),*])
+    };
+}
+
+pub fn translate(id: &str, english: &str, args: &[(&str, TrArg)]) -> String {
+    let catalog = catalog();
+    if let Some(c) = &catalog {
+        if let Some(m) = c.messages.get(id) {
+            return substitute(m, args, Some(c));
+        }
+        if c.reported_missing.borrow_mut().insert(id.to_string()) {
+            println!("warning, {} has no translation for {id}, it'll be in English", c.lang);
+        }
+    }
+    substitute(english, args, None)
+}
+
+fn substitute(template: &str, args: &[(&str, TrArg)], catalog: Option<&Catalog>) -> String {
+    let mut r = String::new();
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        r.push_str(&rest[..open]);
+        let Some(close) = rest[open..].find('}') else {
+            // a brace that's never closed is just text
+            r.push_str(&rest[open..]);
+            return r;
+        };
+        let inner = rest[open + 1..open + close].trim();
+        match inner.strip_prefix('$') {
+            Some(reference) => {
+                let (name, form) = match reference.split_once('-') {
+                    Some((n, f)) => (n, Some(f)),
+                    None => (reference, None),
+                };
+                let Some((_, value)) = args.iter().find(|(n, _)| *n == name) else {
+                    panic!("{template:?} refers to ${name}, which wasn't given");
+                };
+                match value {
+                    TrArg::Text(t) => r.push_str(t),
+                    TrArg::Element(e) => r.push_str(&element_form(*e, form, catalog)),
+                }
+            }
+            // an icon, or just a brace
+            None => r.push_str(&rest[open..open + close + 1]),
+        }
+        rest = &rest[open + close + 1..];
+    }
+    r.push_str(rest);
+    r
+}
+
+fn element_form(e: ElementTag, form: Option<&str>, catalog: Option<&Catalog>) -> String {
+    let name = ELEMENT_NAMES[e];
+    if let Some(c) = catalog {
+        let with_form = form.map(|f| format!("element-{name}-{f}"));
+        if let Some(v) = with_form.and_then(|k| c.messages.get(&k)) {
+            return v.clone();
+        }
+        if let Some(v) = c.messages.get(&format!("element-{name}")) {
+            return v.clone();
+        }
+    }
+    match form {
+        Some("plural") => ELEMENT_NAMES_PLURAL[e],
+        Some("singular") => ELEMENT_NAMES_SINGULAR[e],
+        Some("article") => ELEMENT_ARTICLE[e],
+        _ => name,
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalog(text: &str) -> Catalog {
+        let path = std::env::temp_dir().join(format!("cardgen-test-{}.ftl", unique_id("catalog")));
+        std::fs::write(&path, text).unwrap();
+        let c = Catalog::load(&path, "xx");
+        std::fs::remove_file(&path).unwrap();
+        c
+    }
+
+    #[test]
+    fn loads_catalogs() {
+        let c = catalog(
+            "# a comment\nend-altruism = Deine Werte\n    umfassen die Werte\n\n    der anderen.\n\n\nend-each = { $scores } Punkt\n",
+        );
+        assert_eq!(c.messages["end-altruism"], "Deine Werte\numfassen die Werte\n\nder anderen.");
+        assert_eq!(c.messages["end-each"], "{ $scores } Punkt");
+        assert_eq!(c.messages.len(), 2);
+    }
+
+    #[test]
+    fn substitutes_arguments() {
+        let c = catalog("element-field = Feld\nelement-field-dative = einem Feld\nelement-lake = See\n");
+        let args = [("n", TrArg::from("2")), ("element", TrArg::Element(FIELD))];
+        assert_eq!(substitute("{ $n } mit { $element-dative }", &args, Some(&c)), "2 mit einem Feld");
+        // forms the catalog doesn't have fall back to the element's plain name
+        let lake = [("element", TrArg::Element(LAKE))];
+        assert_eq!(substitute("{ $element-dative }", &lake, Some(&c)), "See");
+        // and without a catalog, to the English forms
+        assert_eq!(substitute("{ $element-plural }", &lake, None), ELEMENT_NAMES_PLURAL[LAKE]);
+        // icons and other braces are left alone
+        assert_eq!(substitute("{kill} a { $n }", &args, None), "{kill} a 2");
+        assert_eq!(substitute("a { b", &args, None), "a { b");
+        assert_eq!(substitute("{ $n } {", &args, None), "2 {");
+    }
+}
@@ -1,12 +1,11 @@
 #![feature(let_chains, coroutines, iter_from_coroutine, extract_if)]
 
 use std::{
-    collections::HashMap,
     fs::{create_dir, read_dir, remove_file, File},
     io::Write,
     ops::Deref,
     os::unix::ffi::OsStrExt,
-    path::Path,
+    path::{Path, PathBuf},
     process::Command,
     rc::Rc,
 };
@@ -15,6 +14,30 @@ use std::{
 mod boring;
 pub use boring::*;
 mod generation;
+mod board;
+pub use board::*;
+mod board_code;
+pub use board_code::*;
+mod text_layout;
+pub use text_layout::*;
+mod thegamecrafter;
+pub use thegamecrafter::*;
+mod tabletop_simulator;
+pub use tabletop_simulator::*;
+mod contact_sheet;
+pub use contact_sheet::*;
+mod gallery;
+pub use gallery::*;
+mod colorblind;
+pub use colorblind::*;
+mod localization;
+pub use localization::*;
+mod svg_tree;
+pub use svg_tree::*;
+mod pdf_text;
+pub use pdf_text::*;
+#[cfg(test)]
+mod visual_regression;
 use noisy_float::prelude::*;
 
 use mako_infinite_shuffle::{rng::LFSRFNTimes, Indexing, OpsRef, Shuffled};
@@ -42,8 +65,20 @@ struct Conf {
     gen_back: bool,
     final_gen: Option<Box<FinalGenConf>>,
     print_and_play_gen: Option<Box<PnpGen>>,
+    // pngs, a manifest and a zip to upload to thegamecrafter
+    tgc_export: Option<Box<TgcExportConf>>,
+    // deck sheets and a saved object for playtesting in tabletop simulator
+    tts_export: Option<Box<TtsExportConf>>,
+    // every card on one sheet per deck, for reviewing
+    contact_sheets: Option<Box<ContactSheetConf>>,
+    // an html catalogue of the cards
+    gallery: Option<Box<GalleryConf>>,
     output: String,
     check_frequencies: bool,
+    // bleed and the safe zone overlay, for the card svgs. Print and play sheets ignore it.
+    card_print: CardPrintConf,
+    // puts a distinct glyph on every element graphic, for players who can't tell the colors apart
+    colorblind_marks: bool,
 }
 impl Default for Conf {
     fn default() -> Self {
@@ -53,8 +88,14 @@ impl Default for Conf {
             gen_back: true,
             final_gen: None,
             print_and_play_gen: None,
+            tgc_export: None,
+            tts_export: None,
+            contact_sheets: None,
+            gallery: None,
             check_frequencies: false,
             output: "generated_card_svgs".to_string(),
+            card_print: CardPrintConf::default(),
+            colorblind_marks: false,
         }
     }
 }
@@ -78,6 +119,43 @@ fn make_land_counts(spurt: usize, modulo: usize, initial_ratios: &[u8]) -> Vec<u
     land_counts
 }
 
+// names a generator's group on the contact sheet by what kind of card it makes, and the first card in it
+fn contact_sheet_label(deck: &str, specs: &[CardSpec]) -> String {
+    let Some(first) = specs.first() else {
+        return deck.to_string();
+    };
+    let kind = match &first.desire {
+        Some(d) => d.kind().to_string(),
+        None => first
+            .properties
+            .iter()
+            .map(|(k, _)| format!("{k:?}").to_lowercase())
+            .collect::<Vec<_>>()
+            .join(" + "),
+    };
+    if kind.is_empty() {
+        format!("{deck}, like {}", first.name)
+    } else {
+        format!("{deck}: {kind}, like {}", first.name)
+    }
+}
+
+// every card that gets printed, besides the lands
+fn printed_card_specs(ends_specs: &[CardGen], means_specs: &[CardGen]) -> Vec<CardSpec> {
+    let mut specs: Vec<CardSpec> = ends_specs
+        .iter()
+        .chain(means_specs.iter())
+        .flat_map(|g| g.generator.iter())
+        .collect();
+    specs.extend(hand_made_cards(Path::new("hand_made_cards/ends"), Deck::Ends));
+    specs.extend(hand_made_cards(Path::new("hand_made_cards/means"), Deck::Means));
+    specs.extend(hand_made_cards(
+        Path::new("hand_made_cards/end events"),
+        Deck::Events,
+    ));
+    specs
+}
+
 fn gen_cards(assets: &Rc<Assets>, conf: &Conf) {
     fn prep_clear_dir(p: &Path) {
         if let Ok(dens) = read_dir(p) {
@@ -92,6 +170,13 @@ fn gen_cards(assets: &Rc<Assets>, conf: &Conf) {
         }
     }
 
+    set_card_print_conf(conf.card_print);
+    set_colorblind_marks(conf.colorblind_marks);
+    if conf.colorblind_marks {
+        for problem in colorblind_contrast_problems() {
+            println!("warning, {problem}");
+        }
+    }
     let ends_specs = generation::end_specs(assets);
     let means_specs = generation::means_specs(assets);
 
@@ -106,21 +191,27 @@ fn gen_cards(assets: &Rc<Assets>, conf: &Conf) {
             let mut w =
                 File::create(output_dir.join(&format!("{}[back].svg", &spec.name))).unwrap();
             (spec.generate_back)(&mut w);
+            for o in take_text_overflows() {
+                println!(
+                    "warning, the back of {} still doesn't fit at {}px: {:?}",
+                    spec.name, o.font_size, o.text
+                );
+            }
         }
     }
 
     if let Some(ref fconf) = conf.final_gen {
         //generates the entire set and winnows them according to the weights of different kinds of cards in the conf
-        let final_means_svgs_path = Path::new("final_means_svgs");
-        let final_ends_svgs_path = Path::new("final_ends_svgs");
-        let final_land_svgs_path = Path::new("final_land_svgs");
-        let final_means_pngs_path = Path::new("final_means_pngs");
-        let final_ends_pngs_path = Path::new("final_ends_pngs");
-        let final_land_pngs_path = Path::new("final_land_pngs");
+        let final_means_svgs_path = &localized("final_means_svgs");
+        let final_ends_svgs_path = &localized("final_ends_svgs");
+        let final_land_svgs_path = &localized("final_land_svgs");
+        let final_means_pngs_path = &localized("final_means_pngs");
+        let final_ends_pngs_path = &localized("final_ends_pngs");
+        let final_land_pngs_path = &localized("final_land_pngs");
         let final_ends_hand_made_svgs_path = Path::new("hand_made_cards/ends");
         let final_means_hand_made_svgs_path = Path::new("hand_made_cards/means");
         let final_endings_hand_made_svgs_path = Path::new("hand_made_cards/end events");
-        let final_endings_hand_made_pngs_path = Path::new("final_endings_pngs");
+        let final_endings_hand_made_pngs_path = &localized("final_endings_pngs");
         // don't bother generating two distinct land decks for this print run, too expensive
         // let final_surplus_land_svgs_path = Path::new("final_surplus_land_svgs");
         // let final_surplus_land_pngs_path = Path::new("final_surplus_land_pngs");
@@ -292,7 +383,7 @@ fn gen_cards(assets: &Rc<Assets>, conf: &Conf) {
             );
         }
     } else {
-        let debug_output_dir = Path::new(&conf.output);
+        let debug_output_dir = &localized(&conf.output);
         prep_clear_dir(debug_output_dir);
         // generates just a small sample (gen_count) of the possible cards for checking
         let land_specs = generation::land_specs_smaller(&assets, &[1, 1, 1, 1]);
@@ -315,90 +406,128 @@ fn gen_cards(assets: &Rc<Assets>, conf: &Conf) {
             conf.gen_back && conf.gen_front && conf.final_gen.is_some(),
             "you don't have enough dependencies activated to generate pnp"
         );
-        let print_and_play_svgs = Path::new("print_and_play_svgs");
+        let print_and_play_svgs = &localized("print_and_play_svgs");
+        let print_and_play_pdfs = &localized("print_and_play");
+        let mut sheets: Vec<(String, Vec<PathBuf>)> = Vec::new();
         if pnpconf.gen_svgs {
-            //because of hand_drawn_cards, we have to read from the generated svg files instead of just using cardspecs
-            clear_or_create(Path::new("print_and_play"));
-            // [TODO] rewrite this to generate a fresh svg instead of copying svgs in from files, so that we can sort the cardspecs by level and clown status? We could just leave hand-made cards out?
-            let gather_from = |path, cards: &mut Vec<(usize, Rc<Asset>, Rc<Asset>)>| {
-                let mut by_name = HashMap::new();
-                let dens = read_dir(Path::new(path)).unwrap();
-                for item_m in dens {
-                    if let Ok(item) = item_m {
-                        let fns =
-                            String::from_utf8(Vec::from(item.file_name().as_bytes())).unwrap();
-                        let mut namesplit = fns.split("[");
-                        let name = namesplit.next().unwrap();
-                        let within_brackets = namesplit.next().unwrap().split("]").next().unwrap();
-                        let is_front = within_brackets.contains("face");
-                        let repeat: Option<usize> = within_brackets
-                            .split(",")
-                            .nth(1)
-                            .map(|ns| (ns.split("]").next().unwrap()).parse().unwrap());
-                        let a = Rc::new(load_asset(item.path().as_path(), None));
-                        let entry = by_name
-                            .entry(name.to_string())
-                            .or_insert((None, None, None));
-                        if let Some(r) = repeat {
-                            entry.0 = Some(r);
-                        }
-                        let inserting_to = if is_front { &mut entry.1 } else { &mut entry.2 };
-                        *inserting_to = Some(a)
-                    }
+            // the sheets are laid out for cards with just the usual bleed, and the pnpmask covers that up anyway
+            let sheet_conf = CardPrintConf {
+                low_ink: pnpconf.low_ink,
+                ..CardPrintConf::default()
+            };
+            sheets = with_card_print_conf(sheet_conf, || {
+                let mut specs = printed_card_specs(&ends_specs, &means_specs);
+
+                let doing_lands = true; //you could parametize this and do a pnp for people who want land tiles and people who don't... but that's a bother just to save one printed page. They can also just not print that page lmao.
+                if doing_lands {
+                    // lands get sheets of their own, so pad them out to fill those
+                    let (tx, ty) = pnpconf.grid(assets.pnp_mask(pnpconf.low_ink).bounds);
+                    let land_counts = make_land_counts(
+                        0,
+                        tx * ty,
+                        &conf.final_gen.as_ref().unwrap().land_counts,
+                    );
+                    specs.extend(generation::land_specs_card(assets, &land_counts)[0].generator.iter());
                 }
-                for (s, c) in by_name.into_iter() {
-                    if !c.1.is_some() {
-                        println!("front missing on {s}");
-                    }
-                    if !c.2.is_some() {
-                        println!("back missing on {s}");
-                    }
 
-                    cards.push((c.0.unwrap_or(1), c.1.unwrap(), c.2.unwrap()));
+                specs.sort_by(|a, b| {
+                    (a.deck, a.level, a.clown, &a.name).cmp(&(b.deck, b.level, b.clown, &b.name))
+                });
+                let mut groups: Vec<(String, Vec<PnpCard>)> = Vec::new();
+                for spec in specs.iter() {
+                    let group = spec.pnp_group();
+                    if groups.last().is_none_or(|g| g.0 != group) {
+                        groups.push((group, Vec::new()));
+                    }
+                    let render = |generate: &dyn Fn(&mut dyn Write)| {
+                        let mut data = Vec::new();
+                        generate(&mut data);
+                        Rc::new(asset_from_svg_data(&data, &spec.name, None))
+                    };
+                    let front = render(&*spec.generate_front);
+                    let back = render(&*spec.generate_back);
+                    groups.last_mut().unwrap().1.push((spec.repeat, front, back));
                 }
-            };
 
-            let mut cards: Vec<(usize, Rc<Asset>, Rc<Asset>)> = Vec::new();
-            gather_from(Path::new("final_ends_svgs"), &mut cards);
-            gather_from(Path::new("final_means_svgs"), &mut cards);
-            gather_from(Path::new("hand_made_cards/ends"), &mut cards);
-            gather_from(Path::new("hand_made_cards/means"), &mut cards);
-            gather_from(Path::new("hand_made_cards/end events"), &mut cards);
-
-            let doing_lands = true; //you could parametize this and do a pnp for people who want land tiles and people who don't... but that's a bother just to save one printed page. They can also just not print that page lmao.
-            if doing_lands {
-                //generate our card-shaped land svgs
-                let land_path = Path::new("land_as_cards");
-                clear_or_create(land_path);
-                let modulo = 6 * 6;
-                let land_counts = make_land_counts(
-                    cards.iter().map(|c| c.0).sum::<usize>() % modulo,
-                    modulo,
-                    &conf.final_gen.as_ref().unwrap().land_counts,
-                );
-                for spec in generation::land_specs_card(&assets, &land_counts)[0]
-                    .generator
-                    .iter()
-                {
-                    write_spec(&spec, conf, land_path);
-                }
-                gather_from(land_path, &mut cards);
-            }
+                print_and_play_sheets(assets, &groups, print_and_play_svgs, pnpconf)
+            });
+        }
 
-            print_and_play_sheets(
-                &assets,
-                cards.into_iter(),
-                print_and_play_svgs,
-                pnpconf.cutlines_on,
-            );
+        if pnpconf.gen_pdf {
+            assert!(pnpconf.gen_svgs, "the pnp pdfs are made from the sheet svgs");
+            let fonts = get_fonts();
+            // everything, and then each group on its own, for people who only want to print some of it
+            let all: Vec<PathBuf> = sheets.iter().flat_map(|(_, p)| p.iter().cloned()).collect();
+            svgs_to_pdf(&all, &localized("print_and_play.pdf"), &fonts);
+            clear_or_create(print_and_play_pdfs);
+            for (group, pages) in sheets.iter() {
+                svgs_to_pdf(pages, &print_and_play_pdfs.join(format!("{group}.pdf")), &fonts);
+            }
         }
 
         if pnpconf.gen_pngs {
-            let png_path = Path::new("print_and_play_pngs");
+            let png_path = &localized("print_and_play_pngs");
             clear_or_create(png_path);
-            render_pngs_with_from_to(print_and_play_svgs, png_path, svg_to_png_using_inkscape);
+            render_pngs_with_from_to(print_and_play_svgs, png_path, default_svg_to_png);
         }
     }
+
+    if let Some(ref tgcconf) = conf.tgc_export {
+        export_for_thegamecrafter(
+            &printed_card_specs(&ends_specs, &means_specs),
+            tgcconf,
+            &get_fonts(),
+        );
+    }
+
+    if let Some(ref csconf) = conf.contact_sheets {
+        clear_or_create(&localized(&csconf.output_dir));
+        let fonts = get_fonts();
+        let groups = |name: &str, gens: &[CardGen], hand_made: &str, deck: Deck| {
+            let mut r: Vec<(String, Vec<CardSpec>)> = gens
+                .iter()
+                .map(|g| {
+                    let specs: Vec<CardSpec> = g.generator.iter().collect();
+                    (contact_sheet_label(name, &specs), specs)
+                })
+                .collect();
+            r.push((
+                format!("hand made {name}"),
+                hand_made_cards(&Path::new("hand_made_cards").join(hand_made), deck),
+            ));
+            r
+        };
+        contact_sheet("ends", &groups("ends", &ends_specs, "ends", Deck::Ends), csconf, &fonts);
+        contact_sheet("means", &groups("means", &means_specs, "means", Deck::Means), csconf, &fonts);
+        contact_sheet(
+            "events",
+            &groups("events", &[], "end events", Deck::Events),
+            csconf,
+            &fonts,
+        );
+        contact_sheet(
+            "land",
+            &groups("land", &generation::land_specs_card(assets, &[1, 1, 1, 1]), "land", Deck::Land),
+            csconf,
+            &fonts,
+        );
+    }
+
+    if let Some(ref gconf) = conf.gallery {
+        let mut specs = printed_card_specs(&ends_specs, &means_specs);
+        specs.extend(generation::land_specs_card(assets, &[1, 1, 1, 1])[0].generator.iter());
+        write_gallery(&specs, gconf);
+    }
+
+    if let Some(ref ttsconf) = conf.tts_export {
+        let mut specs = printed_card_specs(&ends_specs, &means_specs);
+        let land_counts = conf
+            .final_gen
+            .as_ref()
+            .map_or(FinalGenConf::default().land_counts, |f| f.land_counts.clone());
+        specs.extend(generation::land_specs_card(assets, &land_counts)[0].generator.iter());
+        export_for_tabletop_simulator(assets, &specs, ttsconf, &get_fonts());
+    }
 }
 
 fn demo_boards(assets: &Rc<Assets>) {
@@ -420,6 +549,7 @@ fn demo_boards(assets: &Rc<Assets>) {
         weights: &Vec<f64>,
         rad: usize,
         count: usize,
+        constraints: &BoardConstraints,
         output_dir: &Path,
     ) {
         let mut weights_str = Vec::new();
@@ -428,12 +558,18 @@ fn demo_boards(assets: &Rc<Assets>) {
         }
         let ws = String::from_utf8(weights_str).unwrap();
         for i in 0..count {
+            let params = BoardParams {
+                weights: weights.clone(),
+                radius: rad,
+                suppress_voids: true,
+                seed: i as u64,
+                constraints: constraints.clone(),
+            };
+            println!("board{i}: {}", params.code());
             generation::generate_board(
                 &assets,
-                &weights,
-                rad,
-                true,
-                i as u64,
+                &params,
+                &BoardAnnotations::default(),
                 &mut File::create(&output_dir.join(format!("{}board{i}.svg", &ws))).unwrap(),
             );
         }
@@ -443,36 +579,207 @@ fn demo_boards(assets: &Rc<Assets>) {
         &vec![12.7, 7.0, 6.0, 5.0],
         3,
         6,
+        &BoardConstraints {
+            max_cluster_size: Some(4),
+            min_each_element: Some(1),
+            ..BoardConstraints::default()
+        },
         &Path::new("boards"),
     );
 }
 
+fn boards_from_codes(assets: &Rc<Assets>, args: &[String]) {
+    let output_dir = Path::new("boards");
+    let mut annotations = BoardAnnotations::default();
+    let mut codes = Vec::new();
+    let mut args = args.iter();
+    while let Some(a) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| panic!("{a} needs a value after it"));
+        match a.as_str() {
+            "--colorblind" => set_colorblind_marks(true),
+            "--coords" => annotations.coordinates = true,
+            "--agent" => annotations.agents.push(BoardAgent::parse(value()).unwrap_or_else(|e| panic!("{e}"))),
+            "--highlight" => annotations.highlight = Some(Desire::parse(value()).unwrap_or_else(|e| panic!("{e}"))),
+            o if o.starts_with("--") => panic!("the board command doesn't know {o}"),
+            code => codes.push(code),
+        }
+    }
+    drop(create_dir(output_dir));
+    for code in codes {
+        match BoardParams::from_code(code) {
+            Ok(params) => {
+                let code = params.code();
+                let path = output_dir.join(format!("{code}.svg"));
+                generation::generate_board(
+                    assets,
+                    &params,
+                    &annotations,
+                    &mut File::create(&path).unwrap(),
+                );
+                println!("board {code} written to {}", path.display());
+            }
+            Err(e) => println!("{e}"),
+        }
+    }
+}
+
 use rand::{rngs::StdRng, RngCore, SeedableRng};
 use resvg::usvg::fontdb::Database;
 
-fn get_fonts() -> Database {
+// rubik goes in fonts/ (see fonts/readme.md) so that rendering doesn't depend on what's installed. They're the static instances, the variable font renders at its default weight, which is light, which is why resvg used to look faint.
+pub const FONTS_DIR: &str = "fonts";
+pub fn get_fonts() -> Database {
     let mut fonts = Database::new();
-    // fonts
-    //     .load_font_file(&Path::new("Rubik-VariableFont_wght.ttf"))
-    //     .unwrap();
+    fonts.load_fonts_dir(FONTS_DIR);
+    if !fonts
+        .faces()
+        .any(|f| f.families.iter().any(|(name, _)| name == "Rubik"))
+    {
+        panic!("Rubik isn't in {FONTS_DIR}/ (see {FONTS_DIR}/readme.md), the cards can't be drawn without it");
+    }
+    // only for fallbacks, the faces in fonts/ were loaded first so they take precedence
     fonts.load_system_fonts();
+    fonts.set_sans_serif_family("Rubik");
     fonts
 }
 fn svg_to_png_using_resvg(p: &Path, output: &Path, fonts: &Database) {
+    svg_data_to_png(
+        &std::fs::read(p).unwrap(),
+        &p.to_string_lossy(),
+        output,
+        fonts,
+        None,
+    );
+}
+// size is in pixels, and defaults to the svg's own size. The svg is stretched to fill it.
+fn svg_data_to_png(
+    data: &[u8],
+    name: &str,
+    output: &Path,
+    fonts: &Database,
+    size: Option<(u32, u32)>,
+) {
+    svg_data_to_pixmap(data, name, fonts, size)
+        .save_png(output)
+        .unwrap();
+}
+fn svg_data_to_pixmap(
+    data: &[u8],
+    name: &str,
+    fonts: &Database,
+    size: Option<(u32, u32)>,
+) -> resvg::tiny_skia::Pixmap {
     use resvg::{
         tiny_skia,
         usvg::{Options, Tree},
     };
-    let svgdata = Tree::from_data(&std::fs::read(p).unwrap(), &Options::default(), fonts).unwrap();
-    let pixmap_size = svgdata.size().to_int_size();
-    let mut pixmap = tiny_skia::Pixmap::new(pixmap_size.width(), pixmap_size.height()).unwrap();
+    let options = Options {
+        font_family: "Rubik".to_string(),
+        ..Options::default()
+    };
+    let svgdata = Tree::from_data(data, &options, fonts)
+        .unwrap_or_else(|e| panic!("couldn't parse {name}. {:?}", e));
+    let natural = svgdata.size();
+    let (width, height) = size.unwrap_or_else(|| {
+        let s = natural.to_int_size();
+        (s.width(), s.height())
+    });
+    let mut pixmap = tiny_skia::Pixmap::new(width, height).unwrap();
     resvg::render(
         &svgdata,
-        tiny_skia::Transform::default(),
+        tiny_skia::Transform::from_scale(
+            width as f32 / natural.width(),
+            height as f32 / natural.height(),
+        ),
         &mut pixmap.as_mut(),
     );
-    pixmap.save_png(output).unwrap();
+    pixmap
 }
+// the svgs' units are written as mm but they're really 1/79.375in, which makes our pixels (96 to the css inch) 300 to the real inch
+pub const DESIGN_DPI: f32 = 300.0;
+
+// one page per svg, in the order given, each at its physical size. The fonts are embedded, subsetted, so the pdf doesn't depend on any being installed where it's printed, and its text can be searched and copied. See pdf_text.rs for which text svg2pdf still outlines.
+fn svgs_to_pdf(pages: &[PathBuf], output: &Path, fonts: &Database) {
+    use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref};
+    use svg2pdf::usvg::{self, PostProcessingSteps, TreeParsing, TreePostProc};
+    let mut next_ref = Ref::new(1);
+    let catalog_id = next_ref.bump();
+    let page_tree_id = next_ref.bump();
+    let mut pdf = Pdf::new();
+    let mut page_ids = Vec::new();
+    let mut embedded = EmbeddedFonts::default();
+    for p in pages {
+        let options = usvg::Options {
+            font_family: "Rubik".to_string(),
+            ..usvg::Options::default()
+        };
+        let mut tree = usvg::Tree::from_data(&std::fs::read(p).unwrap(), &options)
+            .unwrap_or_else(|e| panic!("couldn't parse {:?}. {:?}", p, e));
+        tree.postprocess(PostProcessingSteps::default(), fonts);
+        // in points
+        let width = tree.size.width() / DESIGN_DPI * 72.0;
+        let height = tree.size.height() / DESIGN_DPI * 72.0;
+        let mut content = Content::new();
+        let mut graphics = Vec::new();
+        let mut page_fonts = Vec::new();
+        for piece in split_out_text(&tree, DESIGN_DPI, fonts, &mut embedded, &mut next_ref) {
+            match piece {
+                PagePiece::Graphic(graphic) => {
+                    let graphic_id = next_ref.bump();
+                    next_ref = svg2pdf::convert_tree_into(
+                        &graphic,
+                        svg2pdf::Options {
+                            dpi: DESIGN_DPI,
+                            ..svg2pdf::Options::default()
+                        },
+                        &mut pdf,
+                        graphic_id,
+                    );
+                    let name = format!("S{}", graphics.len());
+                    content
+                        .save_state()
+                        .transform([width, 0.0, 0.0, height, 0.0, 0.0])
+                        .x_object(Name(name.as_bytes()))
+                        .restore_state();
+                    graphics.push((name, graphic_id));
+                }
+                PagePiece::Text(runs) => {
+                    page_fonts.extend(runs.iter().map(|r| r.font));
+                    write_runs(&mut content, &runs);
+                }
+            }
+        }
+        page_fonts.sort();
+        page_fonts.dedup();
+        let page_id = next_ref.bump();
+        let content_id = next_ref.bump();
+        page_ids.push(page_id);
+        let mut page = pdf.page(page_id);
+        page.media_box(Rect::new(0.0, 0.0, width, height));
+        page.parent(page_tree_id);
+        page.contents(content_id);
+        let mut resources = page.resources();
+        let mut x_objects = resources.x_objects();
+        for (name, id) in &graphics {
+            x_objects.pair(Name(name.as_bytes()), *id);
+        }
+        x_objects.finish();
+        let mut font_resources = resources.fonts();
+        for &f in &page_fonts {
+            font_resources.pair(Name(font_name(f).as_bytes()), embedded.reference(f));
+        }
+        font_resources.finish();
+        resources.finish();
+        page.finish();
+        pdf.stream(content_id, &content.finish());
+    }
+    embedded.write(&mut pdf, &mut next_ref, fonts);
+    pdf.catalog(catalog_id).pages(page_tree_id);
+    let page_count = page_ids.len() as i32;
+    pdf.pages(page_tree_id).kids(page_ids).count(page_count);
+    std::fs::write(output, pdf.finish()).unwrap();
+}
+
 pub fn svg_to_png_using_inkscape(input: &Path, output: &Path, _fonts: &Database) {
     let mut c = Command::new("inkscape");
     c.arg("--export-type=png");
@@ -481,8 +788,8 @@ pub fn svg_to_png_using_inkscape(input: &Path, output: &Path, _fonts: &Database)
     c.arg(output);
     c.output().unwrap();
 }
-fn default_svg_to_png(input: &Path, output: &Path, _fonts: &Database) {
-    svg_to_png_using_inkscape(input, output, _fonts);
+fn default_svg_to_png(input: &Path, output: &Path, fonts: &Database) {
+    svg_to_png_using_resvg(input, output, fonts);
 }
 
 fn render_pngs_with_resvg() {
@@ -617,7 +924,36 @@ fn gen_store_background(conf: &Conf, assets: &Assets) {
 fn main() {
     // you should set LAND_THEME here if you want a different one, it will be locked in by assets::load, then used in gen_cards. EG: *LAND_THEME.get_mut() = LandTheme { ... }. Also feel free to define your land theme as a constant in boring.rs if you think it's good.
     // "but mako, the rust way is to pass configuration state as a parameter". No, I'm not rewriting every single fucking function call to take another parameter. A better thing than global state would be silent implicits, propagation down the function call stack rather than up
-    let assets = Rc::new(Assets::load(Path::new("assets")));
+    // `cardgen board K7QA-2XM5 ...` deals the boards with those codes into boards/. `--coords` labels the tiles with their coordinates, `--colorblind` marks each tile's element with a glyph, `--agent 1,-2:mage:0` stands an agent of team 0 on that tile (it can be given more than once), and `--highlight chain:field:forest:lake` rings the tiles that earn that desire its points (see Desire::parse).
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    // `--asset-overlay themes/night` draws with the svgs in that directory in place of the ones in assets/ with the same names. It can be given more than once, later overlays win.
+    let mut overlays: Vec<PathBuf> = Vec::new();
+    while let Some(i) = args.iter().position(|a| a == "--asset-overlay") {
+        let dir = args.get(i + 1).expect("--asset-overlay needs a directory").clone();
+        args.drain(i..i + 2);
+        overlays.push(PathBuf::from(dir));
+    }
+    let assets = Rc::new(Assets::load_with_overlays(Path::new("assets"), &overlays));
+    // `--lang de` writes the cards' texts in German, from lang/de.ftl, into localized/de/ (see localization.rs)
+    if let Some(i) = args.iter().position(|a| a == "--lang") {
+        let lang = args.get(i + 1).expect("--lang needs a language, eg --lang de").clone();
+        args.drain(i..i + 2);
+        set_lang(&lang);
+    }
+    // `--format poker` lays the cards out for poker sized cards (or bridge, tarot, square, see CardFormat) and writes them into formats/poker/
+    if let Some(i) = args.iter().position(|a| a == "--format") {
+        let name = args.get(i + 1).expect("--format needs a card format, eg --format poker").clone();
+        args.drain(i..i + 2);
+        let format = CardFormat::by_name(&name).unwrap_or_else(|| {
+            let names: Vec<&str> = CARD_FORMATS.iter().map(|f| f.name).collect();
+            panic!("there's no card format called {name:?}, there's {}", names.join(", "))
+        });
+        set_card_format(format);
+    }
+    if args.first().map(|a| a.as_str()) == Some("board") {
+        boards_from_codes(&assets, &args[1..]);
+        return;
+    }
     // it's convenient to set this to false when you're debugging so that you can just quickly generate the svgs and check them
     let gen_pngs = false;
     let conf = Conf {
@@ -632,8 +968,22 @@ fn main() {
         print_and_play_gen: Some(Box::new(PnpGen {
             gen_svgs: true,
             gen_pngs,
+            gen_pdf: true,
             cutlines_on: true,
+            // true for sheets that are kinder to home printers
+            low_ink: false,
+            ..PnpGen::default()
         })),
+        // Some(Box::new(TgcExportConf::default())) to make an upload for thegamecrafter. It takes a while.
+        tgc_export: None,
+        // Some(Box::new(TtsExportConf::default())) to make decks for tabletop simulator
+        tts_export: None,
+        // Some(Box::new(ContactSheetConf::default())) to get an overview of every card in contact_sheets/
+        contact_sheets: None,
+        // Some(Box::new(GalleryConf::default())) to write cards.html
+        gallery: None,
+        // true to mark every element with a glyph as well as a color
+        colorblind_marks: false,
         ..Conf::default()
     };
     if true {gen_cards(&assets, &conf);}
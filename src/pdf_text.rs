@@ -0,0 +1,571 @@
+// svg2pdf 0.10 can only draw text as outlines, which can't be searched or copied and make the pdfs bigger, so the text that's simple enough to lay out here (horizontal, a solid opaque fill, no stroke, decorations or per character positions) is taken out of each page and written with pdf text operators instead, in a subset of its font embedded in the pdf. It's shaped with the same rustybuzz and the same rules as usvg's own layout, so it lands where the outlines would have. Anything else is still outlined by svg2pdf.
+
+use std::collections::{BTreeMap, HashMap};
+
+use pdf_writer::{
+    types::{CidFontType, FontFlags, SystemInfo, UnicodeCmap},
+    Content, Filter, Finish, Name, Pdf, Rect, Ref, Str,
+};
+use resvg::usvg::fontdb::{self, Database, ID};
+use svg2pdf::usvg::{
+    self, BaselineShift, FontStretch, FontStyle, Group, Node, Opacity, Paint, Text, TextAnchor,
+    TextFlow, TextSpan, Transform, Tree, Visibility, WritingMode,
+};
+
+// a page is drawn as these, in order, so that text stays under anything that was drawn over it
+pub enum PagePiece {
+    Graphic(Box<Tree>),
+    Text(Vec<GlyphRun>),
+}
+// glyphs that share a font, size and color, drawn from one point
+pub struct GlyphRun {
+    // index into EmbeddedFonts
+    pub font: usize,
+    pub size: f32,
+    pub color: [f32; 3],
+    // from text space to the page, in points
+    pub transform: Transform,
+    // each glyph, and how much shorter its advance is than the font's own, in thousandths of an em, as TJ takes it
+    pub glyphs: Vec<(u16, f32)>,
+}
+
+#[derive(Default)]
+pub struct EmbeddedFonts {
+    fonts: Vec<EmbeddedFont>,
+    by_id: HashMap<ID, usize>,
+}
+struct EmbeddedFont {
+    id: ID,
+    // the Type0 font dictionary, allocated as soon as a page uses the font
+    reference: Ref,
+    // the text each glyph was drawn for, for the ToUnicode map
+    glyphs: BTreeMap<u16, String>,
+}
+
+impl EmbeddedFonts {
+    fn index(&mut self, id: ID, next_ref: &mut Ref) -> usize {
+        *self.by_id.entry(id).or_insert_with(|| {
+            self.fonts.push(EmbeddedFont {
+                id,
+                reference: next_ref.bump(),
+                glyphs: BTreeMap::new(),
+            });
+            self.fonts.len() - 1
+        })
+    }
+    pub fn reference(&self, font: usize) -> Ref {
+        self.fonts[font].reference
+    }
+    // writes every font the pages used, subsetted down to the glyphs they drew
+    pub fn write(&self, pdf: &mut Pdf, next_ref: &mut Ref, fonts: &Database) {
+        for (i, font) in self.fonts.iter().enumerate() {
+            // the font was looked at for some text that then had to be outlined after all
+            if font.glyphs.is_empty() {
+                continue;
+            }
+            fonts.with_face_data(font.id, |data, index| {
+                let face = ttf_parser::Face::parse(data, index).unwrap();
+                let per_em = 1000.0 / face.units_per_em() as f32;
+                let postscript_name = face
+                    .names()
+                    .into_iter()
+                    .filter(|n| n.name_id == ttf_parser::name_id::POST_SCRIPT_NAME)
+                    .find_map(|n| n.to_string())
+                    .unwrap_or_else(|| "Font".to_string());
+                // the six letter tag marks it as a subset, it just has to differ between the subsets in one file
+                let tag: String = (0..6)
+                    .rev()
+                    .map(|d| (b'A' + (i / 26usize.pow(d) % 26) as u8) as char)
+                    .collect();
+                let base_font = format!("{tag}+{postscript_name}");
+                let base_font = Name(base_font.as_bytes());
+                let system_info = SystemInfo {
+                    registry: Str(b"Adobe"),
+                    ordering: Str(b"Identity"),
+                    supplement: 0,
+                };
+
+                let cid_id = next_ref.bump();
+                let descriptor_id = next_ref.bump();
+                let file_id = next_ref.bump();
+                let cmap_id = next_ref.bump();
+
+                pdf.type0_font(font.reference)
+                    .base_font(base_font)
+                    .encoding_predefined(Name(b"Identity-H"))
+                    .descendant_font(cid_id)
+                    .to_unicode(cmap_id);
+
+                let mut cid = pdf.cid_font(cid_id);
+                cid.subtype(CidFontType::Type2)
+                    .base_font(base_font)
+                    .system_info(system_info)
+                    .font_descriptor(descriptor_id)
+                    .default_width(0.0)
+                    .cid_to_gid_map_predefined(Name(b"Identity"));
+                let mut widths = cid.widths();
+                for &g in font.glyphs.keys() {
+                    let advance = face.glyph_hor_advance(ttf_parser::GlyphId(g)).unwrap_or(0);
+                    widths.consecutive(g, [advance as f32 * per_em]);
+                }
+                widths.finish();
+                cid.finish();
+
+                let bbox = face.global_bounding_box();
+                let mut flags = FontFlags::NON_SYMBOLIC;
+                if face.is_italic() {
+                    flags |= FontFlags::ITALIC;
+                }
+                if face.is_monospaced() {
+                    flags |= FontFlags::FIXED_PITCH;
+                }
+                pdf.font_descriptor(descriptor_id)
+                    .name(base_font)
+                    .flags(flags)
+                    .bbox(Rect::new(
+                        bbox.x_min as f32 * per_em,
+                        bbox.y_min as f32 * per_em,
+                        bbox.x_max as f32 * per_em,
+                        bbox.y_max as f32 * per_em,
+                    ))
+                    .italic_angle(face.italic_angle().unwrap_or(0.0))
+                    .ascent(face.ascender() as f32 * per_em)
+                    .descent(face.descender() as f32 * per_em)
+                    .cap_height(face.capital_height().unwrap_or(face.ascender()) as f32 * per_em)
+                    // there's no good source for it in the font, this is the usual guess from the weight
+                    .stem_v(10.0 + 0.244 * (face.weight().to_number() as f32 - 50.0))
+                    .font_file2(file_id);
+
+                let used: Vec<u16> = font.glyphs.keys().copied().collect();
+                let subset = subsetter::subset(data, index, subsetter::Profile::pdf(&used))
+                    .unwrap_or_else(|e| panic!("couldn't subset {postscript_name}. {e:?}"));
+                pdf.stream(
+                    file_id,
+                    &miniz_oxide::deflate::compress_to_vec_zlib(&subset, 6),
+                )
+                .filter(Filter::FlateDecode);
+
+                let mut cmap = UnicodeCmap::new(Name(b"Custom"), system_info);
+                for (&g, text) in &font.glyphs {
+                    cmap.pair_with_multiple(g, text.chars());
+                }
+                pdf.cmap(cmap_id, &cmap.finish());
+            });
+        }
+    }
+}
+
+pub fn font_name(font: usize) -> String {
+    format!("F{font}")
+}
+
+// the tree has to have been postprocessed, for the absolute transforms. dpi is the tree's, as given to svg2pdf.
+pub fn split_out_text(
+    tree: &Tree,
+    dpi: f32,
+    fonts: &Database,
+    embedded: &mut EmbeddedFonts,
+    next_ref: &mut Ref,
+) -> Vec<PagePiece> {
+    // the same mapping svg2pdf draws the tree with, from the svg's user space to the page in points
+    let scale = 72.0 / dpi;
+    let page = Transform::from_row(1.0, 0.0, 0.0, -1.0, 0.0, tree.size.height() * scale)
+        .pre_scale(scale, scale)
+        .pre_concat(usvg::utils::view_box_to_transform(
+            tree.view_box.rect,
+            tree.view_box.aspect,
+            tree.size,
+        ));
+
+    // every node that isn't a group with children, in drawing order, with the text that could be laid out here
+    let mut leaves: Vec<Option<Vec<GlyphRun>>> = Vec::new();
+    collect_leaves(&tree.root, true, &mut |node, plain| {
+        leaves.push(match node {
+            Node::Text(text) if plain => lay_out(text, page, fonts, embedded, next_ref),
+            _ => None,
+        })
+    });
+
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    for (i, leaf) in leaves.into_iter().enumerate() {
+        if let Some(runs) = leaf {
+            if start < i {
+                pieces.push(PagePiece::Graphic(Box::new(graphic_between(
+                    tree, start, i,
+                ))));
+            }
+            start = i + 1;
+            match pieces.last_mut() {
+                Some(PagePiece::Text(previous)) => previous.extend(runs),
+                _ => pieces.push(PagePiece::Text(runs)),
+            }
+        }
+    }
+    if start < leaf_count(&tree.root) || pieces.is_empty() {
+        pieces.push(PagePiece::Graphic(Box::new(graphic_between(
+            tree,
+            start,
+            usize::MAX,
+        ))));
+    }
+    pieces
+}
+
+// plain is whether nothing above the node changes how it's composited, text under an opacity, clip, mask or filter is left to svg2pdf
+fn collect_leaves(group: &Group, plain: bool, f: &mut impl FnMut(&Node, bool)) {
+    for node in &group.children {
+        match node {
+            Node::Group(g) if !g.children.is_empty() => {
+                let plain = plain
+                    && g.opacity == Opacity::ONE
+                    && g.blend_mode == usvg::BlendMode::Normal
+                    && !g.isolate
+                    && g.clip_path.is_none()
+                    && g.mask.is_none()
+                    && g.filters.is_empty();
+                collect_leaves(g, plain, f)
+            }
+            _ => f(node, plain),
+        }
+    }
+}
+fn leaf_count(group: &Group) -> usize {
+    let mut count = 0;
+    collect_leaves(group, true, &mut |_, _| count += 1);
+    count
+}
+
+// a copy of the tree with only the leaves in start..end
+fn graphic_between(tree: &Tree, start: usize, end: usize) -> Tree {
+    fn keep(group: &mut Group, next: &mut usize, start: usize, end: usize) {
+        group.children.retain_mut(|node| match node {
+            Node::Group(g) if !g.children.is_empty() => {
+                keep(g, next, start, end);
+                !g.children.is_empty()
+            }
+            _ => {
+                *next += 1;
+                (start..end).contains(&(*next - 1))
+            }
+        });
+    }
+    let mut tree = tree.clone();
+    keep(&mut tree.root, &mut 0, start, end);
+    tree
+}
+
+// None if the text needs anything this doesn't do
+fn lay_out(
+    text: &Text,
+    page: Transform,
+    fonts: &Database,
+    embedded: &mut EmbeddedFonts,
+    next_ref: &mut Ref,
+) -> Option<Vec<GlyphRun>> {
+    let zero = |v: &Vec<f32>| v.iter().all(|&d| d == 0.0);
+    if text.writing_mode != WritingMode::LeftToRight
+        || !zero(&text.dx)
+        || !zero(&text.dy)
+        || !zero(&text.rotate)
+    {
+        return None;
+    }
+    let mut runs = Vec::new();
+    // the glyph texts are only recorded once the whole text is known to work
+    let mut glyph_texts = Vec::new();
+    let (mut last_x, mut last_y) = (0.0, 0.0);
+    for chunk in &text.chunks {
+        if !matches!(chunk.text_flow, TextFlow::Linear) {
+            return None;
+        }
+        let x = chunk.x.unwrap_or(last_x);
+        let y = chunk.y.unwrap_or(last_y);
+
+        // like usvg, the whole chunk is shaped in each span's font, and each span takes its own glyphs from that
+        let mut glyphs: Vec<Option<(usize, Shaped)>> = Vec::new();
+        for (s, span) in chunk.spans.iter().enumerate() {
+            if !simple_span(span) {
+                return None;
+            }
+            let shaped = shape(&chunk.text, resolve_font(span, fonts)?, span, fonts)?;
+            if glyphs.is_empty() {
+                glyphs = vec![None; shaped.len()];
+            } else if glyphs.len() != shaped.len() {
+                return None;
+            }
+            for (i, g) in shaped.into_iter().enumerate() {
+                if (span.start..span.end).contains(&g.byte) {
+                    glyphs[i] = Some((s, g));
+                }
+            }
+        }
+        let glyphs: Vec<(usize, Shaped)> = glyphs.into_iter().flatten().collect();
+        // one glyph per cluster, with no offsets, is all latin text needs, and keeps the advances simple
+        if glyphs.windows(2).any(|w| w[0].1.byte == w[1].1.byte)
+            || glyphs.iter().any(|(_, g)| g.id == 0 || g.offset)
+        {
+            return None;
+        }
+
+        let advance = |(s, g): &(usize, Shaped)| g.advance * chunk.spans[*s].font_size.get();
+        let width: f32 = glyphs.iter().map(advance).sum();
+        let x = x + match chunk.anchor {
+            TextAnchor::Start => 0.0,
+            TextAnchor::Middle => -width / 2.0,
+            TextAnchor::End => -width,
+        };
+        let mut pen = 0.0;
+        for (i, glyph) in glyphs.iter().enumerate() {
+            let (s, g) = glyph;
+            let span = &chunk.spans[*s];
+            let font = embedded.index(g.font, next_ref);
+            let continues = i > 0 && glyphs[i - 1].0 == *s;
+            if !continues {
+                let Some(Paint::Color(c)) = span.fill.as_ref().map(|f| &f.paint) else {
+                    return None;
+                };
+                runs.push(GlyphRun {
+                    font,
+                    size: span.font_size.get(),
+                    color: [c.red, c.green, c.blue].map(|v| v as f32 / 255.0),
+                    transform: page
+                        .pre_concat(text.abs_transform)
+                        .pre_translate(x + pen, y)
+                        .pre_scale(1.0, -1.0),
+                    glyphs: Vec::new(),
+                });
+            }
+            runs.last_mut().unwrap().glyphs.push((g.id, g.shortfall));
+            let end = glyphs.get(i + 1).map_or(chunk.text.len(), |(_, n)| n.byte);
+            glyph_texts.push((font, g.id, chunk.text[g.byte..end].to_string()));
+            pen += advance(glyph);
+        }
+        last_x = x + width;
+        last_y = y;
+    }
+    for (font, id, t) in glyph_texts {
+        embedded.fonts[font].glyphs.entry(id).or_insert(t);
+    }
+    Some(runs)
+}
+
+fn simple_span(span: &TextSpan) -> bool {
+    span.fill
+        .as_ref()
+        .is_some_and(|f| matches!(f.paint, Paint::Color(_)) && f.opacity == Opacity::ONE)
+        && span.stroke.is_none()
+        && span.visibility == Visibility::Visible
+        && !span.small_caps
+        && span.decoration.underline.is_none()
+        && span.decoration.overline.is_none()
+        && span.decoration.line_through.is_none()
+        && span.dominant_baseline == usvg::DominantBaseline::Auto
+        && matches!(
+            span.alignment_baseline,
+            usvg::AlignmentBaseline::Auto | usvg::AlignmentBaseline::Baseline
+        )
+        && span
+            .baseline_shift
+            .iter()
+            .all(|b| matches!(b, BaselineShift::Baseline))
+        && span.letter_spacing == 0.0
+        && span.word_spacing == 0.0
+        && span.text_length.is_none()
+}
+
+// the same query usvg makes, serif being its last resort
+fn resolve_font(span: &TextSpan, fonts: &Database) -> Option<ID> {
+    let mut families: Vec<fontdb::Family> = span
+        .font
+        .families
+        .iter()
+        .map(|f| match f.as_str() {
+            "serif" => fontdb::Family::Serif,
+            "sans-serif" => fontdb::Family::SansSerif,
+            "cursive" => fontdb::Family::Cursive,
+            "fantasy" => fontdb::Family::Fantasy,
+            "monospace" => fontdb::Family::Monospace,
+            name => fontdb::Family::Name(name),
+        })
+        .collect();
+    families.push(fontdb::Family::Serif);
+    fonts.query(&fontdb::Query {
+        families: &families,
+        weight: fontdb::Weight(span.font.weight),
+        stretch: match span.font.stretch {
+            FontStretch::UltraCondensed => fontdb::Stretch::UltraCondensed,
+            FontStretch::ExtraCondensed => fontdb::Stretch::ExtraCondensed,
+            FontStretch::Condensed => fontdb::Stretch::Condensed,
+            FontStretch::SemiCondensed => fontdb::Stretch::SemiCondensed,
+            FontStretch::Normal => fontdb::Stretch::Normal,
+            FontStretch::SemiExpanded => fontdb::Stretch::SemiExpanded,
+            FontStretch::Expanded => fontdb::Stretch::Expanded,
+            FontStretch::ExtraExpanded => fontdb::Stretch::ExtraExpanded,
+            FontStretch::UltraExpanded => fontdb::Stretch::UltraExpanded,
+        },
+        style: match span.font.style {
+            FontStyle::Normal => fontdb::Style::Normal,
+            FontStyle::Italic => fontdb::Style::Italic,
+            FontStyle::Oblique => fontdb::Style::Oblique,
+        },
+    })
+}
+
+#[derive(Clone)]
+struct Shaped {
+    font: ID,
+    id: u16,
+    // the byte in the chunk's text its cluster starts at
+    byte: usize,
+    // in ems
+    advance: f32,
+    // thousandths of an em
+    shortfall: f32,
+    offset: bool,
+}
+
+fn shape(text: &str, font: ID, span: &TextSpan, fonts: &Database) -> Option<Vec<Shaped>> {
+    fonts.with_face_data(font, |data, index| {
+        let face = rustybuzz::Face::from_slice(data, index)?;
+        // only truetype outlines go in a FontFile2, cff fonts are left to svg2pdf
+        face.tables().glyf?;
+        let per_em = face.units_per_em() as f32;
+        let mut buffer = rustybuzz::UnicodeBuffer::new();
+        buffer.push_str(text);
+        buffer.set_direction(rustybuzz::Direction::LeftToRight);
+        let mut features = Vec::new();
+        if !span.apply_kerning {
+            features.push(rustybuzz::Feature::new(
+                rustybuzz::Tag::from_bytes(b"kern"),
+                0,
+                ..,
+            ));
+        }
+        let output = rustybuzz::shape(&face, &features, buffer);
+        Some(
+            output
+                .glyph_positions()
+                .iter()
+                .zip(output.glyph_infos())
+                .map(|(pos, info)| {
+                    let id = info.glyph_id as u16;
+                    let natural =
+                        face.glyph_hor_advance(ttf_parser::GlyphId(id)).unwrap_or(0) as f32;
+                    Shaped {
+                        font,
+                        id,
+                        byte: info.cluster as usize,
+                        advance: pos.x_advance as f32 / per_em,
+                        shortfall: (natural - pos.x_advance as f32) * 1000.0 / per_em,
+                        offset: pos.x_offset != 0 || pos.y_offset != 0,
+                    }
+                })
+                .collect(),
+        )
+    })?
+}
+
+pub fn write_runs(content: &mut Content, runs: &[GlyphRun]) {
+    content.begin_text();
+    for run in runs {
+        let [r, g, b] = run.color;
+        let t = run.transform;
+        content
+            .set_fill_rgb(r, g, b)
+            .set_font(Name(font_name(run.font).as_bytes()), run.size)
+            .set_text_matrix([t.sx, t.ky, t.kx, t.sy, t.tx, t.ty]);
+        let mut show = content.show_positioned();
+        let mut items = show.items();
+        for (i, &(id, shortfall)) in run.glyphs.iter().enumerate() {
+            items.show(Str(&id.to_be_bytes()));
+            // the adjustment moves the next glyph, so the last one's doesn't matter
+            if shortfall != 0.0 && i + 1 < run.glyphs.len() {
+                items.adjust(shortfall);
+            }
+        }
+    }
+    content.end_text();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_fonts;
+    use svg2pdf::usvg::{PostProcessingSteps, TreeParsing, TreePostProc};
+
+    fn pieces(svg: &str) -> (Vec<PagePiece>, Tree) {
+        let fonts = get_fonts();
+        let options = usvg::Options {
+            font_family: "Rubik".to_string(),
+            ..usvg::Options::default()
+        };
+        let mut tree = Tree::from_str(svg, &options).unwrap();
+        tree.postprocess(PostProcessingSteps::default(), &fonts);
+        let pieces = split_out_text(
+            &tree,
+            96.0,
+            &fonts,
+            &mut EmbeddedFonts::default(),
+            &mut Ref::new(1),
+        );
+        (pieces, tree)
+    }
+    fn shape(pieces: &[PagePiece]) -> Vec<&str> {
+        pieces
+            .iter()
+            .map(|p| match p {
+                PagePiece::Graphic(_) => "graphic",
+                PagePiece::Text(_) => "text",
+            })
+            .collect()
+    }
+
+    #[test]
+    fn text_keeps_its_place_between_graphics() {
+        let (pieces, _) = pieces(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+            <rect width="100" height="100" fill="#fff"/>
+            <g><text x="10" y="50" font-size="20" fill="#000">salt</text><text x="10" y="80" font-size="20" fill="#000">ice</text></g>
+            <rect width="10" height="10" fill="#f00"/>
+            </svg>"##,
+        );
+        assert_eq!(shape(&pieces), ["graphic", "text", "graphic"]);
+        let PagePiece::Text(runs) = &pieces[1] else {
+            unreachable!()
+        };
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].glyphs.len(), 4);
+    }
+
+    #[test]
+    fn text_under_opacity_is_left_to_svg2pdf() {
+        let (pieces, _) = pieces(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+            <g opacity="0.5"><text x="10" y="50" font-size="20" fill="#000">salt</text></g>
+            <text x="10" y="80" font-size="20" stroke="#000">ice</text>
+            </svg>"##,
+        );
+        assert_eq!(shape(&pieces), ["graphic"]);
+    }
+
+    #[test]
+    fn lands_where_usvg_put_it() {
+        let (pieces, tree) = pieces(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100">
+            <text x="100" y="30" font-size="20" text-anchor="middle" fill="#000">Wave to <tspan font-weight="bold">AVA</tspan></text>
+            </svg>"##,
+        );
+        let Node::Text(text) = &tree.root.children[0] else {
+            unreachable!()
+        };
+        let PagePiece::Text(runs) = &pieces[0] else {
+            unreachable!()
+        };
+        // at 96 dpi a px is 0.75pt, and the page's y runs up from the bottom
+        let left = text.bounding_box.unwrap().left() * 0.75;
+        assert!((runs[0].transform.tx - left).abs() < 0.01);
+        assert!((runs[0].transform.ty - (100.0 - 30.0) * 0.75).abs() < 0.01);
+        assert_eq!(runs.len(), 2);
+    }
+}
@@ -0,0 +1,319 @@
+// a small svg tree, for putting card structure together out of parts rather than big templates. Build the tree, then write it out once (Element is Display). Parts drawn by the older helpers that write straight to a Write (assets, Displaying closures) go in as Raw markup.
+// since the tree exists before it's written, things can be done to it afterwards, walk_mut visits every element, and check finds structural problems, like two elements with the same id.
+
+use std::{fmt::Display, io::Write};
+
+use super::*;
+
+pub enum Node {
+    Element(Element),
+    // markup that's already been written, it's inserted as is
+    Raw(String),
+    // character data, eg the contents of a tspan. It's escaped.
+    Text(String),
+}
+impl From<Element> for Node {
+    fn from(e: Element) -> Self {
+        Node::Element(e)
+    }
+}
+
+pub struct Element {
+    pub tag: String,
+    pub attrs: Vec<(String, String)>,
+    pub children: Vec<Node>,
+}
+
+pub fn el(tag: &str) -> Element {
+    Element {
+        tag: tag.to_string(),
+        attrs: Vec::new(),
+        children: Vec::new(),
+    }
+}
+pub fn g() -> Element {
+    el("g")
+}
+// an inkscape layer, which is what most of our templates were pasted from
+pub fn layer() -> Element {
+    g().attr("inkscape:label", "Layer 1")
+        .attr("inkscape:groupmode", "layer")
+}
+pub fn path(d: &str) -> Element {
+    el("path").attr("d", d)
+}
+pub fn polygon(points: &str) -> Element {
+    el("polygon").attr("points", points)
+}
+pub fn rect(ul: V2, span: V2) -> Element {
+    el("rect")
+        .attr("x", ul.x)
+        .attr("y", ul.y)
+        .attr("width", span.x)
+        .attr("height", span.y)
+}
+pub fn circle(center: V2, r: f64) -> Element {
+    el("circle")
+        .attr("cx", center.x)
+        .attr("cy", center.y)
+        .attr("r", r)
+}
+pub fn text(at: V2) -> Element {
+    el("text")
+        .attr("xml:space", "preserve")
+        .attr("x", at.x)
+        .attr("y", at.y)
+}
+pub fn tspan(at: V2, content: &str) -> Element {
+    el("tspan")
+        .attr("x", at.x)
+        .attr("y", at.y)
+        .child(Node::Text(content.to_string()))
+}
+// captures whatever draw writes
+pub fn raw(draw: impl FnOnce(&mut dyn Write)) -> Node {
+    let mut buffer = Vec::new();
+    draw(&mut buffer);
+    Node::Raw(String::from_utf8(buffer).unwrap())
+}
+pub fn raw_display(d: &impl Display) -> Node {
+    Node::Raw(d.to_string())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Transform {
+    Translate(V2),
+    Scale(f64),
+    // in degrees, about the origin
+    Rotate(f64),
+    // in degrees, about the given point
+    RotateAbout(f64, V2),
+    // a b c d e f, as in svg
+    Matrix([f64; 6]),
+}
+impl Display for Transform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Transform::Translate(v) => write!(f, "translate({},{})", v.x, v.y),
+            Transform::Scale(s) => write!(f, "scale({s})"),
+            Transform::Rotate(r) => write!(f, "rotate({r})"),
+            Transform::RotateAbout(r, c) => write!(f, "rotate({r},{},{})", c.x, c.y),
+            Transform::Matrix([a, b, c, d, e, ff]) => write!(f, "matrix({a},{b},{c},{d},{e},{ff})"),
+        }
+    }
+}
+
+impl Element {
+    pub fn attr(mut self, name: &str, value: impl Display) -> Self {
+        self.set_attr(name, value);
+        self
+    }
+    pub fn id(self, id: &str) -> Self {
+        self.attr("id", id)
+    }
+    pub fn style(self, style: impl Display) -> Self {
+        self.attr("style", style)
+    }
+    pub fn transform(self, transforms: &[Transform]) -> Self {
+        let t: Vec<String> = transforms.iter().map(|t| t.to_string()).collect();
+        self.attr("transform", t.join(" "))
+    }
+    pub fn child(mut self, c: impl Into<Node>) -> Self {
+        self.children.push(c.into());
+        self
+    }
+    pub fn children(mut self, cs: impl IntoIterator<Item = Node>) -> Self {
+        self.children.extend(cs);
+        self
+    }
+    pub fn get_attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+    // replaces the attribute if it's already there, so that it keeps its place
+    pub fn set_attr(&mut self, name: &str, value: impl Display) {
+        let value = value.to_string();
+        match self.attrs.iter_mut().find(|(n, _)| n == name) {
+            Some(a) => a.1 = value,
+            None => self.attrs.push((name.to_string(), value)),
+        }
+    }
+    // this element and every element under it, parents first
+    pub fn walk_mut(&mut self, f: &mut dyn FnMut(&mut Element)) {
+        f(self);
+        for c in self.children.iter_mut() {
+            if let Node::Element(e) = c {
+                e.walk_mut(f);
+            }
+        }
+    }
+    fn walk<'a>(&'a self, f: &mut dyn FnMut(&'a Element)) {
+        f(self);
+        for c in self.children.iter() {
+            if let Node::Element(e) = c {
+                e.walk(f);
+            }
+        }
+    }
+    // ids that are used by more than one element, and url(#..)/href references to ids that no element has. Raw markup isn't looked into, so references from the tree into raw markup will be reported.
+    pub fn check(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        let mut ids: Vec<&str> = Vec::new();
+        self.walk(&mut |e| {
+            if let Some(id) = e.get_attr("id") {
+                ids.push(id);
+            }
+        });
+        let mut sorted = ids.clone();
+        sorted.sort();
+        for pair in sorted.windows(2) {
+            if pair[0] == pair[1] && !problems.iter().any(|p: &String| p.contains(pair[0])) {
+                problems.push(format!("more than one element has the id {:?}", pair[0]));
+            }
+        }
+        self.walk(&mut |e| {
+            for (name, value) in e.attrs.iter() {
+                let mut references = Vec::new();
+                let mut rest = value.as_str();
+                while let Some(at) = rest.find("url(#") {
+                    rest = &rest[at + 5..];
+                    if let Some(end) = rest.find(')') {
+                        references.push(&rest[..end]);
+                    }
+                }
+                if name.ends_with("href") {
+                    if let Some(r) = value.strip_prefix('#') {
+                        references.push(r);
+                    }
+                }
+                for r in references {
+                    if !ids.contains(&r) {
+                        problems.push(format!("<{}> refers to {r:?}, which isn't in the tree", e.tag));
+                    }
+                }
+            }
+        });
+        problems
+    }
+    // reads markup back into a tree, eg a finished card, so that it can be checked. Namespaced names get the prefixes we write them with.
+    pub fn parse(svg: &str) -> Result<Element, String> {
+        let root = elementtree::Element::from_reader(svg.as_bytes()).map_err(|e| e.to_string())?;
+        fn name(q: &elementtree::QName) -> String {
+            let prefix = match q.ns() {
+                Some("http://www.w3.org/1999/xlink") => "xlink:",
+                Some("http://www.w3.org/XML/1998/namespace") => "xml:",
+                Some("http://www.inkscape.org/namespaces/inkscape") => "inkscape:",
+                Some("http://sodipodi.sourceforge.net/DTD/sodipodi-0.dtd") => "sodipodi:",
+                _ => "",
+            };
+            format!("{prefix}{}", q.name())
+        }
+        fn convert(e: &elementtree::Element) -> Element {
+            let mut r = el(&name(e.tag()));
+            for (n, v) in e.attrs() {
+                r.set_attr(&name(n), v);
+            }
+            if !e.text().is_empty() {
+                r.children.push(Node::Text(e.text().to_string()));
+            }
+            for c in e.children() {
+                r.children.push(Node::Element(convert(c)));
+                if !c.tail().is_empty() {
+                    r.children.push(Node::Text(c.tail().to_string()));
+                }
+            }
+            r
+        }
+        Ok(convert(&root))
+    }
+    pub fn write_to(&self, to: &mut dyn Write) {
+        write!(to, "{self}").unwrap();
+    }
+}
+
+// escapes text for xml, or html, quotes too if it's going in an attribute
+pub(crate) fn escaped(s: &str, in_attribute: bool) -> String {
+    let s = s.replace('&', "&amp;").replace('<', "&lt;");
+    if in_attribute {
+        s.replace('"', "&quot;")
+    } else {
+        s.replace('>', "&gt;")
+    }
+}
+
+impl Display for Node {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Node::Element(e) => e.fmt(f),
+            Node::Raw(r) => f.write_str(r),
+            Node::Text(t) => f.write_str(&escaped(t, false)),
+        }
+    }
+}
+impl Display for Element {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<{}", self.tag)?;
+        for (name, value) in self.attrs.iter() {
+            write!(f, r#" {name}="{}""#, escaped(value, true))?;
+        }
+        if self.children.is_empty() {
+            return write!(f, " />");
+        }
+        write!(f, ">")?;
+        for c in self.children.iter() {
+            // text has to stay exactly where it is, a tspan's content shouldn't get a newline
+            if matches!(c, Node::Element(_)) && !matches!(self.tag.as_str(), "text" | "tspan") {
+                writeln!(f)?;
+            }
+            c.fmt(f)?;
+        }
+        write!(f, "</{}>", self.tag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_nested_elements() {
+        let tree = g()
+            .id("a")
+            .transform(&[Transform::Translate(V2::new(1.0, 2.0)), Transform::Scale(0.5)])
+            .child(path("M 0,0 H 1").style("fill:#ffffff"))
+            .child(text(V2::new(0.0, 3.0)).child(tspan(V2::new(0.0, 3.0), "a < b")));
+        assert_eq!(
+            tree.to_string(),
+            r##"<g id="a" transform="translate(1,2) scale(0.5)">
+<path d="M 0,0 H 1" style="fill:#ffffff" />
+<text xml:space="preserve" x="0" y="3"><tspan x="0" y="3">a &lt; b</tspan></text></g>"##
+        );
+    }
+
+    #[test]
+    fn finds_duplicate_ids_and_dangling_references() {
+        let tree = g()
+            .child(path("").id("p"))
+            .child(path("").id("p"))
+            .child(g().style("filter:url(#missing)"))
+            .child(el("use").attr("xlink:href", "#p"));
+        let problems = tree.check();
+        assert_eq!(problems.len(), 2, "{problems:?}");
+        assert!(problems[0].contains("\"p\""));
+        assert!(problems[1].contains("\"missing\""));
+    }
+
+    #[test]
+    fn walk_mut_reaches_every_element() {
+        let mut tree = g().child(g().child(path("").style("fill:#000000")));
+        tree.walk_mut(&mut |e| {
+            if let Some(s) = e.get_attr("style") {
+                let s = s.replace("#000000", "#ffffff");
+                e.set_attr("style", s);
+            }
+        });
+        assert!(tree.to_string().contains("fill:#ffffff"));
+    }
+}
@@ -0,0 +1,256 @@
+// exports the decks for tabletop simulator, for playtesting remotely: a sprite sheet of faces and one of backs per (up to) 69 distinct cards, and a saved object that spawns every deck with the right number of each card, plus a dealt board, one hex tile per land, so that lands can be flipped and moved during play.
+// to play, put P1.json in tts's Saved Objects folder and spawn it from Objects > Saved Objects. The images are referred to by their absolute paths, so leave them where they are, tts will offer to upload them to its cloud.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use resvg::{
+    tiny_skia::{Pixmap, PixmapPaint, Transform},
+    usvg::{fontdb::Database, Options, Tree},
+};
+use serde_json::{json, Value};
+
+use super::*;
+
+// tts won't take a deck sheet bigger than this
+const TTS_MAX_COLUMNS: usize = 10;
+const TTS_MAX_ROWS: usize = 7;
+// the last slot of every face sheet is taken by the image that the other players see while a card's in someone's hand
+const TTS_CARDS_PER_SHEET: usize = TTS_MAX_COLUMNS * TTS_MAX_ROWS - 1;
+// CustomTile types
+const TTS_HEX_TILE: u32 = 1;
+// the distance between the centers of neighbouring land tiles, in tts units. A hex tile at scale 1 is about 2 across.
+const TTS_TILE_SCALE: f64 = 1.0;
+const TTS_TILE_SPACING: f64 = 2.0 * TTS_TILE_SCALE;
+const TILE_PIXELS: u32 = 256;
+// enough to read a description zoomed in, the same for every format so that bigger cards just get bigger sheets
+const TTS_PIXELS_PER_INCH: f64 = 240.0;
+
+// the size of the card within its cutline, at TTS_PIXELS_PER_INCH. For mini cards it's 420x600.
+pub fn tts_card_pixels() -> (u32, u32) {
+    let cut = card_format().cut * TTS_PIXELS_PER_INCH;
+    (cut.x.round() as u32, cut.y.round() as u32)
+}
+
+pub struct TtsExportConf {
+    pub output_dir: PathBuf,
+    // the size each card gets on the sheets. They're cropped to their cutlines, tts rounds the corners itself. Defaults to tts_card_pixels, which follows the card format.
+    pub card_pixels: Option<(u32, u32)>,
+    pub board: BoardParams,
+}
+impl Default for TtsExportConf {
+    fn default() -> Self {
+        Self {
+            output_dir: PathBuf::from("tabletop_simulator"),
+            card_pixels: None,
+            board: BoardParams {
+                weights: vec![12.7, 7.0, 6.0, 5.0],
+                radius: 3,
+                suppress_voids: true,
+                seed: 0,
+                constraints: BoardConstraints {
+                    max_cluster_size: Some(4),
+                    min_each_element: Some(1),
+                    ..BoardConstraints::default()
+                },
+            },
+        }
+    }
+}
+
+pub fn export_for_tabletop_simulator(
+    assets: &Rc<Assets>,
+    specs: &[CardSpec],
+    conf: &TtsExportConf,
+    fonts: &Database,
+) {
+    with_card_print_conf(CardPrintConf::default(), || {
+        let out = localized(&conf.output_dir);
+        let out = out.as_path();
+        clear_or_create(out);
+        let card_pixels = conf.card_pixels.unwrap_or_else(tts_card_pixels);
+        let mut decks: BTreeMap<Deck, Vec<&CardSpec>> = BTreeMap::new();
+        for spec in specs.iter() {
+            decks.entry(spec.deck).or_default().push(spec);
+        }
+
+        let mut hidden = Vec::new();
+        blank_front(&"", CARD_BACKGROUND_COLOR, false, &mut hidden);
+        let hidden = ("the hidden card".to_string(), hidden);
+        let mut objects = Vec::new();
+        // tts numbers every sheet in the save, card ids are the sheet's number * 100 + the slot
+        let mut sheet_number = 1;
+        for (i, (deck, mut cards)) in decks.into_iter().enumerate() {
+            cards.sort_by(|a, b| (a.level, a.clown, &a.name).cmp(&(b.level, b.clown, &b.name)));
+            let mut custom_decks = serde_json::Map::new();
+            let mut contained = Vec::new();
+            for (sheeti, sheet) in cards.chunks(TTS_CARDS_PER_SHEET).enumerate() {
+                let columns = (sheet.len() + 1).clamp(2, TTS_MAX_COLUMNS);
+                let rows = (sheet.len() + 1).div_ceil(columns).clamp(2, TTS_MAX_ROWS);
+                let render = |side: &str, generate: &dyn Fn(&CardSpec, &mut Vec<u8>)| -> PathBuf {
+                    let mut cells: Vec<(String, Vec<u8>)> = sheet
+                        .iter()
+                        .map(|s| {
+                            let mut data = Vec::new();
+                            generate(s, &mut data);
+                            (s.name.clone(), data)
+                        })
+                        .collect();
+                    cells.resize(columns * rows - 1, (String::new(), Vec::new()));
+                    cells.push(hidden.clone());
+                    let path = out.join(format!("{}_{side}{sheeti}.png", deck.name()));
+                    sprite_sheet(&cells, columns, card_pixels, fonts).save_png(&path).unwrap();
+                    std::fs::canonicalize(path).unwrap()
+                };
+                let faces = render("faces", &|s, w| (s.generate_front)(w));
+                let backs = render("backs", &|s, w| (s.generate_back)(w));
+                let custom_deck = json!({
+                    "FaceURL": file_url(&faces),
+                    "BackURL": file_url(&backs),
+                    "NumWidth": columns,
+                    "NumHeight": rows,
+                    "BackIsHidden": false,
+                    "UniqueBack": true,
+                    "Type": 0,
+                });
+                custom_decks.insert(sheet_number.to_string(), custom_deck.clone());
+                for (slot, spec) in sheet.iter().enumerate() {
+                    for _ in 0..spec.repeat {
+                        contained.push(json!({
+                            "Name": "Card",
+                            "Nickname": spec.name,
+                            "CardID": sheet_number * 100 + slot,
+                            "CustomDeck": { sheet_number.to_string(): custom_deck },
+                            "Transform": transform(0.0, 0.0, 1.0),
+                        }));
+                    }
+                }
+                sheet_number += 1;
+            }
+            // decks are laid out in a row above the board
+            let at = transform(-6.0 + 3.0 * i as f64, 10.0, 1.0);
+            if contained.len() == 1 {
+                let mut card = contained.pop().unwrap();
+                card["Transform"] = at;
+                objects.push(card);
+            } else {
+                let ids: Vec<Value> = contained.iter().map(|c| c["CardID"].clone()).collect();
+                objects.push(json!({
+                    "Name": "Deck",
+                    "Nickname": deck.name(),
+                    "DeckIDs": ids,
+                    "CustomDeck": custom_decks,
+                    "ContainedObjects": contained,
+                    "Transform": at,
+                }));
+            }
+        }
+
+        // the board's dealt as hex tiles, laid out as generate_board draws it, so that they can be flipped and moved like the real ones
+        let code = conf.board.code();
+        let dealt = conf.board.deal();
+        let mut tile_images: BTreeMap<ElementTag, String> = BTreeMap::new();
+        for (c, e) in dealt.board.tiles.iter() {
+            let image = tile_images.entry(*e).or_insert_with(|| {
+                let path = out.join(format!("tile {}.png", ELEMENT_NAMES[*e]));
+                let span = both_dims(BIG_ELEMENT_RAD * 2.25);
+                let mut tile = Vec::new();
+                do_sheet(
+                    span,
+                    &Displaying(|w| assets.element(*e).centered_rad(span / 2.0, BIG_ELEMENT_RAD, w)),
+                    &mut tile,
+                );
+                svg_data_to_png(&tile, ELEMENT_NAMES[*e], &path, fonts, Some((TILE_PIXELS, TILE_PIXELS)));
+                file_url(&std::fs::canonicalize(path).unwrap())
+            });
+            // down the image is towards the players, which is -z
+            let p = hexify(c.to_v2()).yx() * TTS_TILE_SPACING;
+            objects.push(json!({
+                "Name": "Custom_Tile",
+                "Nickname": ELEMENT_NAMES[*e],
+                "Description": format!("board {code}, {},{}", c.x, c.y),
+                "CustomImage": {
+                    "ImageURL": image,
+                    "ImageSecondaryURL": "",
+                    "CustomTile": {
+                        "Type": TTS_HEX_TILE,
+                        "Thickness": 0.1,
+                        "Stackable": false,
+                        "Stretch": true,
+                    },
+                },
+                "Transform": transform(p.x, -p.y, TTS_TILE_SCALE),
+            }));
+        }
+
+        let save = json!({
+            "SaveName": "P1",
+            "GameMode": "",
+            "Date": "",
+            "Table": "",
+            "Sky": "",
+            "Note": "",
+            "Rules": "",
+            "XmlUI": "",
+            "LuaScript": "",
+            "LuaScriptState": "",
+            "ObjectStates": objects,
+        });
+        std::fs::write(
+            out.join("P1.json"),
+            serde_json::to_string_pretty(&save).unwrap(),
+        )
+        .unwrap();
+    })
+}
+
+fn transform(x: f64, z: f64, scale: f64) -> Value {
+    json!({
+        "posX": x, "posY": 1.0, "posZ": z,
+        "rotX": 0.0, "rotY": 180.0, "rotZ": 0.0,
+        "scaleX": scale, "scaleY": 1.0, "scaleZ": scale,
+    })
+}
+
+fn file_url(path: &Path) -> String {
+    format!("file:///{}", path.to_string_lossy().trim_start_matches('/'))
+}
+
+// each card is cropped to its cutline and scaled into a cell. Empty svgs leave their cell blank.
+fn sprite_sheet(cards: &[(String, Vec<u8>)], columns: usize, cell: (u32, u32), fonts: &Database) -> Pixmap {
+    let rows = cards.len().div_ceil(columns);
+    let mut sheet = Pixmap::new(cell.0 * columns as u32, cell.1 * rows as u32).unwrap();
+    let options = Options {
+        font_family: "Rubik".to_string(),
+        ..Options::default()
+    };
+    let cut = cutline_bounds();
+    for (i, (name, data)) in cards.iter().enumerate().filter(|(_, (_, d))| !d.is_empty()) {
+        let tree = Tree::from_data(data, &options, fonts)
+            .unwrap_or_else(|e| panic!("couldn't read the svg of {name} for the tabletop simulator sheets, {e}"));
+        // the tree's in css pixels, the card in design units
+        let px_per_unit = tree.size().width() / card_dimensions().x as f32;
+        let scale_x = cell.0 as f32 / (cut.width() as f32 * px_per_unit);
+        let scale_y = cell.1 as f32 / (cut.height() as f32 * px_per_unit);
+        let mut card = Pixmap::new(cell.0, cell.1).unwrap();
+        resvg::render(
+            &tree,
+            Transform::from_scale(scale_x, scale_y).pre_translate(
+                -cut.ul.x as f32 * px_per_unit,
+                -cut.ul.y as f32 * px_per_unit,
+            ),
+            &mut card.as_mut(),
+        );
+        sheet.draw_pixmap(
+            ((i % columns) as u32 * cell.0) as i32,
+            ((i / columns) as u32 * cell.1) as i32,
+            card.as_ref(),
+            &PixmapPaint::default(),
+            Transform::identity(),
+            None,
+        );
+    }
+    sheet
+}
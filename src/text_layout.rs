@@ -0,0 +1,402 @@
+// lays out card text ourselves, with the font's real metrics, since the svg2 shape-inside wrapping we used to rely on only works in inkscape
+
+use std::{cell::RefCell, rc::Rc};
+
+use resvg::usvg::fontdb::{Family, Query, Weight};
+
+use super::*;
+
+// in the coordinate space of the description text (px, scaled down to mm by the text's transform). It runs down to near the bottom of the card, so taller card formats get more room.
+pub fn description_rect() -> Rect {
+    let ul = V2::new(73.083376, 74.501079);
+    Rect {
+        ul,
+        br: ul + V2::new(454.10823, 671.09289 + extra_layout_height() / 0.26458333),
+    }
+}
+pub const DESCRIPTION_FONT_SIZE: f64 = 43.0;
+// we'd rather a card overflow than get this small, it'll get reported
+pub const DESCRIPTION_MIN_FONT_SIZE: f64 = 28.0;
+pub const DESCRIPTION_LINE_HEIGHT: f64 = 1.25;
+pub const DESCRIPTION_FONT: &str = "Rubik";
+// used when the font isn't around to be measured. Roughly rubik's average advance and ascent, in ems
+const FALLBACK_ADVANCE: f64 = 0.56;
+const FALLBACK_ASCENDER: f64 = 0.935;
+
+struct FontData {
+    data: Vec<u8>,
+    index: u32,
+}
+
+thread_local! {
+    // loaded the first time any text is laid out. None if the font couldn't be found.
+    static FONT: Rc<Option<FontData>> = Rc::new(load_font(DESCRIPTION_FONT));
+    static TEXT_OVERFLOWS: RefCell<Vec<TextOverflow>> = const { RefCell::new(Vec::new()) };
+}
+
+fn load_font(family: &str) -> Option<FontData> {
+    let fonts = get_fonts();
+    let id = fonts.query(&Query {
+        families: &[Family::Name(family)],
+        weight: Weight::NORMAL,
+        ..Query::default()
+    });
+    let Some(id) = id else {
+        println!("warning, couldn't find {family} for measuring text, guessing at its metrics instead");
+        return None;
+    };
+    fonts.with_face_data(id, |data, index| FontData {
+        data: data.to_vec(),
+        index,
+    })
+}
+
+struct Measurer<'a> {
+    face: Option<ttf_parser::Face<'a>>,
+}
+impl<'a> Measurer<'a> {
+    fn new(font: &'a Option<FontData>) -> Self {
+        Measurer {
+            face: font
+                .as_ref()
+                .and_then(|f| ttf_parser::Face::parse(&f.data, f.index).ok()),
+        }
+    }
+    // in ems
+    fn width(&self, s: &str) -> f64 {
+        match self.face.as_ref() {
+            Some(face) => {
+                let em = face.units_per_em() as f64;
+                s.chars()
+                    .map(|c| {
+                        face.glyph_index(c)
+                            .and_then(|g| face.glyph_hor_advance(g))
+                            .map_or(FALLBACK_ADVANCE, |a| a as f64 / em)
+                    })
+                    .sum()
+            }
+            None => s.chars().count() as f64 * FALLBACK_ADVANCE,
+        }
+    }
+    fn ascender(&self) -> f64 {
+        self.face.as_ref().map_or(FALLBACK_ASCENDER, |face| {
+            face.ascender() as f64 / face.units_per_em() as f64
+        })
+    }
+}
+
+// icons are written inline as {name}, see Assets::icon
+pub enum TextRun {
+    Text(String),
+    Icon(String),
+}
+
+pub struct TextLine {
+    pub runs: Vec<TextRun>,
+    // in ems
+    pub width: f64,
+    pub baseline: f64,
+}
+impl TextLine {
+    fn has_icons(&self) -> bool {
+        self.runs.iter().any(|r| matches!(r, TextRun::Icon(_)))
+    }
+}
+
+pub struct TextLayout {
+    pub font_size: f64,
+    pub lines: Vec<TextLine>,
+    pub fits: bool,
+}
+
+// how much room an inline icon takes up, in ems
+pub const INLINE_ICON_SPAN: f64 = 0.9;
+const INLINE_ICON_MARGIN: f64 = 0.08;
+// how far the bottom of an icon hangs below the baseline, so that round icons look like they're sitting on it
+const INLINE_ICON_DROP: f64 = 0.08;
+
+// splits "{ice}," into [Icon("ice"), Text(",")]. Braces around anything that isn't an icon stay as text.
+fn parse_runs(word: &str, is_icon: &dyn Fn(&str) -> bool) -> Vec<TextRun> {
+    let mut runs = Vec::new();
+    let mut rest = word;
+    while !rest.is_empty() {
+        let icon = rest.strip_prefix('{').and_then(|r| {
+            let close = r.find('}')?;
+            is_icon(&r[..close]).then(|| (&r[..close], &r[close + 1..]))
+        });
+        if let Some((name, after)) = icon {
+            runs.push(TextRun::Icon(name.to_string()));
+            rest = after;
+        } else {
+            // up to the next brace after this character
+            let first = rest.chars().next().unwrap().len_utf8();
+            let split = rest[first..].find('{').map_or(rest.len(), |i| i + first);
+            push_text(&mut runs, &rest[..split]);
+            rest = &rest[split..];
+        }
+    }
+    runs
+}
+
+fn push_text(runs: &mut Vec<TextRun>, s: &str) {
+    if let Some(TextRun::Text(t)) = runs.last_mut() {
+        t.push_str(s);
+    } else {
+        runs.push(TextRun::Text(s.to_string()));
+    }
+}
+
+impl Measurer<'_> {
+    fn runs_width(&self, runs: &[TextRun]) -> f64 {
+        runs.iter()
+            .map(|r| match r {
+                TextRun::Text(t) => self.width(t),
+                TextRun::Icon(_) => INLINE_ICON_SPAN + 2.0 * INLINE_ICON_MARGIN,
+            })
+            .sum()
+    }
+}
+
+// breaks lines greedily at spaces (and always at newlines), shrinking the font a pixel at a time until it fits in the bounds or reaches min_font_size
+pub fn layout_text(
+    text: &str,
+    bounds: Rect,
+    max_font_size: f64,
+    min_font_size: f64,
+    line_height: f64,
+    is_icon: &dyn Fn(&str) -> bool,
+) -> TextLayout {
+    let font = FONT.with(|f| f.clone());
+    let measurer = Measurer::new(&font);
+    let mut font_size = max_font_size;
+    loop {
+        let max_width = bounds.width() / font_size;
+        let mut lines: Vec<Vec<TextRun>> = Vec::new();
+        for paragraph in text.split('\n') {
+            let mut line: Vec<TextRun> = Vec::new();
+            for word in paragraph.split(' ').filter(|w| !w.is_empty()) {
+                let word = parse_runs(word, is_icon);
+                let mut candidate_width = measurer.runs_width(&line) + measurer.runs_width(&word);
+                if !line.is_empty() {
+                    candidate_width += measurer.width(" ");
+                }
+                if !line.is_empty() && candidate_width > max_width {
+                    lines.push(std::mem::take(&mut line));
+                }
+                if !line.is_empty() {
+                    push_text(&mut line, " ");
+                }
+                for r in word {
+                    match r {
+                        TextRun::Text(t) => push_text(&mut line, &t),
+                        icon => line.push(icon),
+                    }
+                }
+            }
+            lines.push(line);
+        }
+        let widths: Vec<f64> = lines.iter().map(|l| measurer.runs_width(l)).collect();
+        let first_baseline = bounds.ul.y + measurer.ascender() * font_size;
+        let step = line_height * font_size;
+        let text_height = measurer.ascender() * font_size + step * (lines.len() as f64 - 1.0);
+        let fits = widths.iter().all(|w| *w <= max_width) && text_height <= bounds.height();
+        if fits || font_size - 1.0 < min_font_size {
+            return TextLayout {
+                font_size,
+                lines: lines
+                    .into_iter()
+                    .zip(widths)
+                    .enumerate()
+                    .map(|(i, (runs, width))| TextLine {
+                        runs,
+                        width,
+                        baseline: first_baseline + step * i as f64,
+                    })
+                    .collect(),
+                fits,
+            };
+        }
+        font_size -= 1.0;
+    }
+}
+
+impl TextLayout {
+    // centered on the bounds horizontally, one tspan per line, or per run of text on lines that have icons in them. The icons have to be drawn separately, outside of the text element, see icons.
+    pub fn write_tspans(&self, bounds: Rect, to: &mut dyn Write) {
+        let font = FONT.with(|f| f.clone());
+        let measurer = Measurer::new(&font);
+        let center = bounds.center().x;
+        for line in self.lines.iter() {
+            let y = line.baseline;
+            if !line.has_icons() {
+                if let Some(TextRun::Text(t)) = line.runs.first() {
+                    write!(to, r##"<tspan x="{center}" y="{y}">{}</tspan>"##, escaped(t, false)).unwrap();
+                }
+                continue;
+            }
+            let mut x = center - line.width * self.font_size / 2.0;
+            for r in line.runs.iter() {
+                match r {
+                    TextRun::Text(t) => {
+                        write!(
+                            to,
+                            r##"<tspan x="{x}" y="{y}" style="text-anchor:start">{}</tspan>"##,
+                            escaped(t, false)
+                        )
+                        .unwrap();
+                        x += measurer.width(t) * self.font_size;
+                    }
+                    TextRun::Icon(_) => {
+                        x += (INLINE_ICON_SPAN + 2.0 * INLINE_ICON_MARGIN) * self.font_size;
+                    }
+                }
+            }
+        }
+    }
+    // the name of each inline icon, and the bounds it should be drawn within
+    pub fn icons(&self, bounds: Rect) -> Vec<(&str, Rect)> {
+        let font = FONT.with(|f| f.clone());
+        let measurer = Measurer::new(&font);
+        let mut r = Vec::new();
+        let center = bounds.center().x;
+        let size = self.font_size;
+        for line in self.lines.iter().filter(|l| l.has_icons()) {
+            let mut x = center - line.width * size / 2.0;
+            for run in line.runs.iter() {
+                match run {
+                    TextRun::Text(t) => x += measurer.width(t) * size,
+                    TextRun::Icon(name) => {
+                        let bottom = line.baseline + INLINE_ICON_DROP * size;
+                        let left = x + INLINE_ICON_MARGIN * size;
+                        r.push((
+                            name.as_str(),
+                            Rect {
+                                ul: V2::new(left, bottom - INLINE_ICON_SPAN * size),
+                                br: V2::new(left + INLINE_ICON_SPAN * size, bottom),
+                            },
+                        ));
+                        x += (INLINE_ICON_SPAN + 2.0 * INLINE_ICON_MARGIN) * size;
+                    }
+                }
+            }
+        }
+        r
+    }
+}
+
+pub struct TextOverflow {
+    pub text: String,
+    pub font_size: f64,
+}
+
+pub fn report_text_overflow(layout: &TextLayout, text: &str) {
+    if !layout.fits {
+        TEXT_OVERFLOWS.with(|o| {
+            o.borrow_mut().push(TextOverflow {
+                text: text.to_string(),
+                font_size: layout.font_size,
+            })
+        });
+    }
+}
+
+// everything that's overflowed since this was last called
+pub fn take_text_overflows() -> Vec<TextOverflow> {
+    TEXT_OVERFLOWS.with(|o| std::mem::take(&mut *o.borrow_mut()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_icons(_: &str) -> bool {
+        false
+    }
+    fn ice_icon(name: &str) -> bool {
+        name == "ice"
+    }
+    // icons as [name], so that runs can be compared as strings
+    fn described(runs: &[TextRun]) -> Vec<String> {
+        runs.iter()
+            .map(|r| match r {
+                TextRun::Text(t) => t.clone(),
+                TextRun::Icon(name) => format!("[{name}]"),
+            })
+            .collect()
+    }
+    fn lines(layout: &TextLayout) -> Vec<String> {
+        layout.lines.iter().map(|l| described(&l.runs).concat()).collect()
+    }
+    // in px at that font size, the same way layout_text measures
+    fn width(s: &str, font_size: f64) -> f64 {
+        let font = FONT.with(|f| f.clone());
+        Measurer::new(&font).width(s) * font_size
+    }
+    fn rect(width: f64, height: f64) -> Rect {
+        Rect { ul: V2::new(0.0, 0.0), br: V2::new(width, height) }
+    }
+
+    #[test]
+    fn breaks_lines_at_the_width() {
+        let bounds = rect(width("aaa bbb", 40.0) + 0.01, 1000.0);
+        let layout = layout_text("aaa bbb ccc", bounds, 40.0, 40.0, 1.25, &no_icons);
+        assert_eq!(lines(&layout), ["aaa bbb", "ccc"]);
+        assert!(layout.fits);
+        assert!((layout.lines[1].baseline - layout.lines[0].baseline - 50.0).abs() < 1e-9);
+        // and always at newlines
+        let layout = layout_text("aaa\n\nbbb", rect(1000.0, 1000.0), 40.0, 40.0, 1.25, &no_icons);
+        assert_eq!(lines(&layout), ["aaa", "", "bbb"]);
+    }
+
+    #[test]
+    fn shrinks_until_it_fits() {
+        // room for one line, and for "abc def" only at 20px
+        let font = FONT.with(|f| f.clone());
+        let ascender = Measurer::new(&font).ascender();
+        let bounds = || rect(width("abc def", 20.0) + 0.01, ascender * 30.0);
+        let layout = layout_text("abc def", bounds(), 30.0, 10.0, 1.25, &no_icons);
+        assert_eq!(layout.font_size, 20.0);
+        assert_eq!(lines(&layout), ["abc def"]);
+        assert!(layout.fits);
+
+        // but not below the minimum, which gets reported
+        take_text_overflows();
+        let layout = layout_text("abc def", bounds(), 30.0, 25.0, 1.25, &no_icons);
+        assert_eq!(layout.font_size, 25.0);
+        assert!(!layout.fits);
+        report_text_overflow(&layout, "abc def");
+        let overflows = take_text_overflows();
+        assert_eq!(overflows.len(), 1);
+        assert_eq!((overflows[0].text.as_str(), overflows[0].font_size), ("abc def", 25.0));
+        assert!(take_text_overflows().is_empty());
+    }
+
+    #[test]
+    fn reads_icons_in_braces() {
+        assert_eq!(described(&parse_runs("{ice},", &ice_icon)), ["[ice]", ","]);
+        // braces around anything else stay as they are
+        assert_eq!(described(&parse_runs("a{ice}b{fire}", &ice_icon)), ["a", "[ice]", "b{fire}"]);
+        assert_eq!(described(&parse_runs("{ice", &ice_icon)), ["{ice"]);
+
+        let layout = layout_text("melt {ice} now", rect(1000.0, 1000.0), 40.0, 40.0, 1.25, &ice_icon);
+        assert_eq!(lines(&layout), ["melt [ice] now"]);
+        let icons = layout.icons(rect(1000.0, 1000.0));
+        assert_eq!(icons.len(), 1);
+        assert_eq!(icons[0].0, "ice");
+        assert!((icons[0].1.width() - INLINE_ICON_SPAN * 40.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn escapes_the_text() {
+        for text in ["salt & <pepper>", "salt & {ice} <pepper>"] {
+            let layout = layout_text(text, rect(1000.0, 1000.0), 40.0, 40.0, 1.25, &ice_icon);
+            let mut tspans = Vec::new();
+            layout.write_tspans(rect(1000.0, 1000.0), &mut tspans);
+            let tspans = String::from_utf8(tspans).unwrap();
+            let parsed = elementtree::Element::from_reader(format!("<text>{tspans}</text>").as_bytes())
+                .unwrap_or_else(|e| panic!("{tspans} isn't xml: {e}"));
+            let read: String = parsed.children().map(|t| t.text()).collect();
+            assert_eq!(read, text.replace("{ice}", ""));
+        }
+    }
+}
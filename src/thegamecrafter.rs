@@ -0,0 +1,113 @@
+// exports the decks the way thegamecrafter wants them: a png for each side of each card at the exact size of their template for the card format, a folder per deck, and a manifest saying how many of each to print, all zipped up so that a print run can be uploaded in one go.
+// lands aren't included, we print those as tiles, which have their own templates.
+
+use std::{
+    fs::{read_dir, File},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use resvg::usvg::fontdb::Database;
+
+use super::*;
+
+// their card templates are the cut size with 1/8in of bleed all round, at 300dpi. That's exactly our card, bleed and all, see CardFormat. For mini cards it's 600x825.
+pub fn tgc_card_pixels() -> (u32, u32) {
+    let inches = card_format().cut + both_dims(2.0 * BLEED_INCHES);
+    ((inches.x * 300.0).round() as u32, (inches.y * 300.0).round() as u32)
+}
+
+pub struct TgcExportConf {
+    pub output_dir: PathBuf,
+    // also writes the output dir into a zip file next to it
+    pub zip: bool,
+}
+impl Default for TgcExportConf {
+    fn default() -> Self {
+        Self {
+            output_dir: PathBuf::from("thegamecrafter"),
+            zip: true,
+        }
+    }
+}
+
+pub fn export_for_thegamecrafter(specs: &[CardSpec], conf: &TgcExportConf, fonts: &Database) {
+    // their template has the bleed in it already, and the safe zone overlay is just for checking
+    with_card_print_conf(CardPrintConf::default(), || {
+        let out = localized(&conf.output_dir);
+        let out = out.as_path();
+        clear_or_create(out);
+        let mut specs: Vec<&CardSpec> = specs.iter().filter(|s| s.deck != Deck::Land).collect();
+        specs.sort_by(|a, b| (a.deck, &a.name).cmp(&(b.deck, &b.name)));
+
+        let mut manifest = String::from("deck,name,quantity,face,back\n");
+        for spec in specs {
+            let deck = spec.deck.name();
+            let deck_dir = out.join(deck);
+            if !deck_dir.exists() {
+                std::fs::create_dir(&deck_dir).unwrap();
+            }
+            let side = |side: &str, generate: &dyn Fn(&mut dyn Write)| -> String {
+                let file_name = format!("{}_{side}.png", spec.name);
+                let mut data = Vec::new();
+                generate(&mut data);
+                svg_data_to_png(
+                    &data,
+                    &spec.name,
+                    &deck_dir.join(&file_name),
+                    fonts,
+                    Some(tgc_card_pixels()),
+                );
+                format!("{deck}/{file_name}")
+            };
+            let face = side("face", &*spec.generate_front);
+            let back = side("back", &*spec.generate_back);
+            manifest.push_str(&format!(
+                "{deck},{},{},{},{}\n",
+                csv_field(&spec.name),
+                spec.repeat,
+                csv_field(&face),
+                csv_field(&back)
+            ));
+        }
+        std::fs::write(out.join("manifest.csv"), manifest).unwrap();
+
+        if conf.zip {
+            zip_dir(out, &out.with_extension("zip"));
+        }
+    })
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+// pngs are already compressed, so they're just stored
+fn zip_dir(dir: &Path, output: &Path) {
+    use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+    let mut z = ZipWriter::new(File::create(output).unwrap());
+    let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+    fn add(z: &mut ZipWriter<File>, root: &Path, dir: &Path, options: FileOptions) {
+        let mut entries: Vec<PathBuf> = read_dir(dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect();
+        entries.sort();
+        for path in entries {
+            let name = path.strip_prefix(root).unwrap().to_string_lossy().to_string();
+            if path.is_dir() {
+                z.add_directory(name, options).unwrap();
+                add(z, root, &path, options);
+            } else {
+                z.start_file(name, options).unwrap();
+                z.write_all(&std::fs::read(&path).unwrap()).unwrap();
+            }
+        }
+    }
+    add(&mut z, dir, dir, options);
+    z.finish().unwrap();
+}
@@ -0,0 +1,181 @@
+// renders every generated card and compares it against the pngs in visual_goldens/, so that a change to a shared helper (tilted_pair, card_outer...) can't quietly change dozens of cards. Cards that differ get a diff image in visual_diffs/: the golden, the new render, and the changed pixels in magenta.
+// after an intended change, look over the diffs, then accept the new renders with
+//   UPDATE_GOLDENS=1 cargo test cards_match_goldens
+// the goldens are drawn with Rubik (see fonts/readme.md), and the test fails if a card's golden is missing.
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use resvg::tiny_skia::{Pixmap, PixmapPaint, Transform};
+
+use super::*;
+
+const GOLDEN_DIR: &str = "visual_goldens";
+const DIFF_DIR: &str = "visual_diffs";
+// relative to print resolution. Half is plenty to see a change in a description, and keeps the goldens small enough to check in.
+const GOLDEN_SCALE: f64 = 0.5;
+// CIE76 distance between the golden's pixel and the new one, below which antialiasing differences (a different resvg, a different cpu) are ignored
+const PIXEL_DELTA_E: f64 = 8.0;
+// a card fails if more than this fraction of its pixels differ
+const MAX_CHANGED_FRACTION: f64 = 0.002;
+const DIFF_COLOR: [u8; 3] = [255, 0, 255];
+
+// the printed cards, hand made ones included, and the lands
+fn checked_specs(assets: &Rc<Assets>) -> Vec<CardSpec> {
+    let mut specs = printed_card_specs(&generation::end_specs(assets), &generation::means_specs(assets));
+    specs.extend(
+        generation::land_specs_smaller(assets, &[1, 1, 1, 1])
+            .iter()
+            .flat_map(|g| g.generator.iter()),
+    );
+    specs
+}
+
+struct Comparison {
+    changed: usize,
+    total: usize,
+    // the changed pixels in magenta over a faded copy of the new render
+    mask: Pixmap,
+}
+
+fn compare(golden: &Pixmap, new: &Pixmap) -> Comparison {
+    let mut mask = Pixmap::new(new.width(), new.height()).unwrap();
+    let mut changed = 0;
+    for ((g, n), m) in golden
+        .pixels()
+        .iter()
+        .zip(new.pixels().iter())
+        .zip(mask.pixels_mut().iter_mut())
+    {
+        // cards are opaque, so the premultiplied colors are the colors
+        let g = g.demultiply();
+        let n = n.demultiply();
+        let as_lab = |c: [u8; 3]| lab(c.map(linear_channel));
+        let d = delta_e(
+            as_lab([g.red(), g.green(), g.blue()]),
+            as_lab([n.red(), n.green(), n.blue()]),
+        );
+        let [r, gr, b] = if d > PIXEL_DELTA_E {
+            changed += 1;
+            DIFF_COLOR
+        } else {
+            // faded, so that the changes stand out
+            [n.red(), n.green(), n.blue()].map(|c| 191 + c / 4)
+        };
+        *m = resvg::tiny_skia::ColorU8::from_rgba(r, gr, b, 255).premultiply();
+    }
+    Comparison {
+        changed,
+        total: new.pixels().len(),
+        mask,
+    }
+}
+
+fn write_diff(path: &Path, golden: &Pixmap, new: &Pixmap, mask: &Pixmap) {
+    let mut sheet = Pixmap::new(new.width() * 3, new.height().max(golden.height())).unwrap();
+    for (i, p) in [golden, new, mask].into_iter().enumerate() {
+        sheet.draw_pixmap(
+            (i as u32 * new.width()) as i32,
+            0,
+            p.as_ref(),
+            &PixmapPaint::default(),
+            Transform::identity(),
+            None,
+        );
+    }
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    sheet.save_png(path).unwrap();
+}
+
+#[test]
+fn cards_match_goldens() {
+    let update = std::env::var("UPDATE_GOLDENS").is_ok_and(|v| v != "0");
+    let fonts = get_fonts();
+    assert!(
+        update || Path::new(GOLDEN_DIR).is_dir(),
+        "there are no goldens in {GOLDEN_DIR}/, make them with UPDATE_GOLDENS=1"
+    );
+    with_card_print_conf(CardPrintConf::default(), || {
+        set_colorblind_marks(false);
+        set_lang("en");
+        let assets = Rc::new(Assets::load(Path::new("assets")));
+        drop(std::fs::remove_dir_all(DIFF_DIR));
+
+        let mut failures: Vec<String> = Vec::new();
+        let mut seen: HashSet<PathBuf> = HashSet::new();
+        for spec in checked_specs(&assets) {
+            for (side, generate) in [("face", &spec.generate_front), ("back", &spec.generate_back)] {
+                let file = Path::new(spec.deck.name()).join(format!("{}[{side}].png", spec.name));
+                if !seen.insert(file.clone()) {
+                    // the same card made by two generators, or several land cards with the same element
+                    continue;
+                }
+                let mut data = Vec::new();
+                generate(&mut data);
+                take_text_overflows();
+                let natural = asset_from_svg_data(&data, &spec.name, None).bounds;
+                // design units are css pixels at print resolution, see DESIGN_DPI
+                let px = 96.0 / 25.4 * GOLDEN_SCALE;
+                let size = (
+                    (natural.x * px).round() as u32,
+                    (natural.y * px).round() as u32,
+                );
+                let new = svg_data_to_pixmap(&data, &spec.name, &fonts, Some(size));
+                let golden_path = Path::new(GOLDEN_DIR).join(&file);
+                if update {
+                    std::fs::create_dir_all(golden_path.parent().unwrap()).unwrap();
+                    new.save_png(&golden_path).unwrap();
+                    continue;
+                }
+                let Ok(golden) = Pixmap::load_png(&golden_path) else {
+                    failures.push(format!("{} has no golden", file.display()));
+                    continue;
+                };
+                let diff_path = Path::new(DIFF_DIR).join(&file);
+                if (golden.width(), golden.height()) != (new.width(), new.height()) {
+                    failures.push(format!(
+                        "{} changed size, from {}x{} to {}x{}",
+                        file.display(),
+                        golden.width(),
+                        golden.height(),
+                        new.width(),
+                        new.height()
+                    ));
+                    write_diff(&diff_path, &golden, &new, &new);
+                    continue;
+                }
+                let c = compare(&golden, &new);
+                if c.changed as f64 > c.total as f64 * MAX_CHANGED_FRACTION {
+                    failures.push(format!(
+                        "{} changed, {:.2}% of its pixels differ",
+                        file.display(),
+                        c.changed as f64 / c.total as f64 * 100.0
+                    ));
+                    write_diff(&diff_path, &golden, &new, &c.mask);
+                }
+            }
+        }
+
+        // goldens of cards that aren't generated any more
+        for deck in read_dir(GOLDEN_DIR).into_iter().flatten().flatten() {
+            for golden in read_dir(deck.path()).into_iter().flatten().flatten() {
+                let file = Path::new(&deck.file_name()).join(golden.file_name());
+                if !update && !seen.contains(&file) {
+                    failures.push(format!("{} is no longer generated", file.display()));
+                } else if update && !seen.contains(&file) {
+                    remove_file(golden.path()).unwrap();
+                }
+            }
+        }
+
+        assert!(
+            failures.is_empty(),
+            "{} cards don't match their goldens (diffs are in {DIFF_DIR}/, if the changes were intended, run with UPDATE_GOLDENS=1):\n{}",
+            failures.len(),
+            failures.join("\n")
+        );
+    })
+}